@@ -1,8 +1,10 @@
 use std::time::Instant;
+use ketchup::asa::{ASA, DequeASA, RopeASA, SoaASA, VectorASA};
 use logos::Logos;
 use rand::Rng;
 
 pub mod nom_parser;
+pub mod storage;
 
 fn main() {
     // generate the benchmarking expression
@@ -12,6 +14,30 @@ fn main() {
     // test them
     println!("ketchup: {}ms", time_ketchup(&expr));
     println!("nom: {}ms", time_nom(&expr));
+
+    // compare the underlying storage of the ASAs, on both the generated expression and a flat chain of operators (where every low-precedence operator gets inserted near the start of the ASA)
+    println!("\nketchup (VectorASA storage): {}ms", time_storage::<VectorASA<_>>(&expr));
+    println!("ketchup (DequeASA storage): {}ms", time_storage::<DequeASA<_>>(&expr));
+    println!("ketchup (RopeASA storage): {}ms", time_storage::<RopeASA<_>>(&expr));
+    println!("ketchup (SoaASA storage): {}ms", time_storage::<SoaASA<_>>(&expr));
+
+    let chain = gen_chain(512 * 1024); // ~512KiB chain (inserting near the start of a `VectorASA` is linear, so a 4MiB chain takes minutes)
+    println!("\nketchup (VectorASA storage, flat chain): {}ms", time_storage::<VectorASA<_>>(&chain));
+    println!("ketchup (DequeASA storage, flat chain): {}ms", time_storage::<DequeASA<_>>(&chain));
+    println!("ketchup (RopeASA storage, flat chain): {}ms", time_storage::<RopeASA<_>>(&chain));
+
+    // and on an adversarial expression, where every operator gets inserted into the middle of the ASA
+    let adversarial = gen_adversarial(512 * 1024); // ~512KiB expression
+    println!("\nketchup (VectorASA storage, adversarial): {}ms", time_storage::<VectorASA<_>>(&adversarial));
+    println!("ketchup (DequeASA storage, adversarial): {}ms", time_storage::<DequeASA<_>>(&adversarial));
+    println!("ketchup (RopeASA storage, adversarial): {}ms", time_storage::<RopeASA<_>>(&adversarial));
+}
+
+fn time_storage<A: ASA<Node = storage::Expr>>(src: &str) -> u128 {
+    let start = Instant::now();
+    let _ = storage::parse::<A>(src);
+
+    (Instant::now() - start).as_millis()
 }
 
 fn time_nom(src: &str) -> u128 {
@@ -101,3 +127,43 @@ pub fn gen_expr(size: usize) -> String {
 
     result
 }
+
+/// Generates an extremely large maths expression with no parentheses
+pub fn gen_chain(size: usize) -> String {
+    let mut result = rand::thread_rng().gen_range(0..256).to_string();
+
+    while result.len() < size {
+        // add a random operator and operand
+        let random = rand::thread_rng().gen_range(0..4);
+        result.push_str(match random {
+            0 => " + ",
+            1 => " - ",
+            2 => " * ",
+            3 => " / ",
+            _ => unreachable!(),
+        });
+        result.push_str(&rand::thread_rng().gen_range(0..256).to_string());
+    }
+
+    result
+}
+
+/// Generates an extremely large maths expression of a chain of low-precedence operators followed by a chain of high-precedence operators,
+/// so every high-precedence operator gets inserted into the middle of the ASA (right after the low-precedence chain)
+pub fn gen_adversarial(size: usize) -> String {
+    let mut result = rand::thread_rng().gen_range(0..256).to_string();
+
+    while result.len() < size {
+        // add a random operator (low-precedence for the first half, high-precedence for the second) and operand
+        let random = rand::thread_rng().gen_range(0..2);
+        result.push_str(match (result.len() < size / 2, random) {
+            (true, 0) => " + ",
+            (true, _) => " - ",
+            (false, 0) => " * ",
+            (false, _) => " / ",
+        });
+        result.push_str(&rand::thread_rng().gen_range(0..256).to_string());
+    }
+
+    result
+}
@@ -82,8 +82,8 @@ fn parse_number(input: &str) -> IResult<&str, Expr> {
 
 #[cfg(test)]
 mod tests {
-    use crate::parser::parse;
-    use crate::types::Expr::*;
+    use super::parse;
+    use super::Expr::*;
 
     #[test]
     fn parse_add_statement() {
@@ -0,0 +1,68 @@
+//! A bare-bones parser that is generic over the ASA, for comparing the underlying storage of different ASAs
+
+use ketchup::{asa::ASA, node::Node, parse};
+use logos::Logos;
+use maths_interpreter::token::Token;
+
+/// An expression 'node' in the ASA
+#[derive(Debug, Clone, Node)]
+pub enum Expr {
+    // operands
+    #[operand]
+    Number(f64),
+    /// A parenthesised expression, as an index into the parsed scopes
+    #[operand]
+    Scoped(usize),
+
+    // unary left-aligned
+    #[unary(prec = 2, align = left)]
+    Pos,
+    #[unary(prec = 2, align = left)]
+    Neg,
+
+    // binary
+    #[binary(prec = 0)]
+    Add,
+    #[binary(prec = 0)]
+    Sub,
+    #[binary(prec = 1)]
+    Mul,
+    #[binary(prec = 1)]
+    Div,
+}
+
+/// Parses a source string into an ASA, alongside the ASAs of all of the parenthesised expressions in it
+pub fn parse<A: ASA<Node = Expr>>(src: &str) -> (A, Vec<A>) {
+    let mut tokens = Token::lexer(src);
+    let mut scopes = Vec::new();
+    let asa = parse_expr(&mut tokens, &mut scopes);
+    (asa, scopes)
+}
+
+/// Parses tokens into an ASA until either there are no more tokens or a closing parenthesis
+fn parse_expr<A: ASA<Node = Expr>>(tokens: &mut logos::Lexer<Token>, scopes: &mut Vec<A>) -> A {
+    let mut asa = A::new(Expr::MAX_PRECEDENCE);
+
+    while let Some(token) = tokens.next() {
+        let node = match token.unwrap() {
+            Token::Number(num) => Expr::Number(num),
+            Token::LParen => {
+                let scope = parse_expr(tokens, scopes);
+                scopes.push(scope);
+                Expr::Scoped(scopes.len() - 1)
+            },
+            Token::RParen => break,
+
+            Token::Plus if !*asa.is_complete() => Expr::Pos,
+            Token::Dash if !*asa.is_complete() => Expr::Neg,
+            Token::Plus => Expr::Add,
+            Token::Dash => Expr::Sub,
+            Token::Star => Expr::Mul,
+            Token::Slash => Expr::Div,
+        };
+        parse::node(node, &mut asa).unwrap();
+    }
+
+    parse::ensure_completed(&mut asa).unwrap();
+    asa
+}
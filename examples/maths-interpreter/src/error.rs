@@ -53,6 +53,10 @@ impl From<KError<'_, Spanned<Expr>>> for Error {
             KError::ExpectedNode(Some(oper)) => Error::ExpectedExpr(oper.clone()),
             KError::ExpectedNode(None) => unreachable!("this error should've been handled manually long before this"),
             KError::UnexpectedExpectedNode { oper, found } => Error::ExpectedExprFoundOther { oper: oper.cloned(), found },
+            KError::ExpectedSeparator(_) | KError::UnexpectedSeparator => unreachable!("there are no mixfix nodes in this maths interpreter"),
+            KError::EmptySlot(_) | KError::TrailingSeparator(_) | KError::ExpectedClose(_) | KError::UnexpectedClose => unreachable!("there are no n-ary nodes in this maths interpreter"),
+            KError::UnexpectedGroup | KError::ExpectedCloseGroup | KError::UnexpectedCloseGroup => unreachable!("parentheses are handled manually by the parser"),
+            KError::CapacityExceeded(_) | KError::GroupCapacityExceeded => unreachable!("the maths interpreter parses into a growable `VectorASA`"),
         }
     }
 }
@@ -1,22 +1,54 @@
 //! Interprets the ASA
 
+use ketchup::{asa::VectorASA, visit::{self, Visitor}};
 use crate::{parser::Expr, span::Spanned};
 
-pub fn walk(asa: &mut impl Iterator<Item = Spanned<Expr>>) -> f64 {
-    let next = asa.next().unwrap(); // alright cuz ASA must be valid
-    match next.item {
-        // operands
-        Expr::Number(num) => num,
-        Expr::Scoped(expr) => walk(&mut expr.item.vector.into_iter()),
-
-        // unary
-        Expr::Pos => walk(asa),
-        Expr::Neg => -walk(asa),
-
-        // binary
-        Expr::Add => walk(asa) + walk(asa),
-        Expr::Sub => walk(asa) - walk(asa),
-        Expr::Mul => walk(asa) * walk(asa),
-        Expr::Div => walk(asa) / walk(asa),
+/// Evaluates the nodes of the ASA
+pub struct Interpreter;
+
+impl Visitor<Spanned<Expr>> for Interpreter {
+    type Output = f64;
+
+    fn visit_operand(&mut self, node: &Spanned<Expr>) -> f64 {
+        match &node.item {
+            Expr::Number(num) => *num,
+            _ => unreachable!(),
+        }
+    }
+
+    fn visit_unary(&mut self, node: &Spanned<Expr>, child: f64) -> f64 {
+        match node.item {
+            Expr::Pos => child,
+            Expr::Neg => -child,
+            _ => unreachable!(),
+        }
+    }
+
+    fn visit_binary(&mut self, node: &Spanned<Expr>, lhs: f64, rhs: f64) -> f64 {
+        match node.item {
+            Expr::Add => lhs + rhs,
+            Expr::Sub => lhs - rhs,
+            Expr::Mul => lhs * rhs,
+            Expr::Div => lhs / rhs,
+            _ => unreachable!(),
+        }
+    }
+
+    fn visit_mixfix(&mut self, _node: &Spanned<Expr>, _params: Vec<f64>) -> f64 {
+        unreachable!()
+    }
+
+    fn visit_nary(&mut self, _node: &Spanned<Expr>, _params: Vec<f64>) -> f64 {
+        unreachable!()
     }
+
+    fn visit_postfix(&mut self, _node: &Spanned<Expr>, _operand: f64, _args: Vec<f64>) -> f64 {
+        unreachable!()
+    }
+}
+
+/// Evaluates a completed ASA
+#[inline]
+pub fn walk(asa: &VectorASA<Spanned<Expr>>) -> f64 {
+    visit::walk(asa, &mut Interpreter)
 }
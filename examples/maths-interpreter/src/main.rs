@@ -8,7 +8,7 @@ fn main() {
     "##;
     let filename = "foo.bar";
 
-    let mut tokens = Token::lexer(&example).spanned();
+    let mut tokens = Token::lexer(example).spanned();
 
     let expr = match parser::parse(&mut tokens, filename) {
         Ok(expr) => expr,
@@ -20,7 +20,7 @@ fn main() {
 
     println!("asa: {expr:#?}");
 
-    let output = interpreter::walk(&mut expr.item.vector.into_iter());
+    let output = interpreter::walk(&expr.item);
 
     println!("output: {output}");
 }
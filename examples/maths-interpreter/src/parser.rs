@@ -1,67 +1,33 @@
 //! Functions for parsing tokens
 
-use ketchup::{asa::{VectorASA, ASA}, node::{Node, NodeKind}, parse, Precedence};
+use ketchup::{asa::{VectorASA, ASA}, error::Error as KError, node::Node, parse};
 use logos::SpannedIter;
 use crate::{error::Error, span::{Span, Spanned}, token::{self, NextTok, NextTokWith, Token}};
 
 /// An expression 'node' in the ASA
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Node)]
 pub enum Expr {
     // operands
+    #[operand]
     Number(f64),
-    Scoped(Box<Spanned<VectorASA<Spanned<Expr>>>>),
 
     // unary left-aligned
+    #[unary(prec = 2, align = left)]
     Pos,
+    #[unary(prec = 2, align = left)]
     Neg,
 
     // binary
+    #[binary(prec = 0)]
     Add,
+    #[binary(prec = 0)]
     Sub,
+    #[binary(prec = 1)]
     Mul,
+    #[binary(prec = 1)]
     Div,
 }
 
-impl Node for Expr {
-    const MAX_PRECEDENCE: Precedence = 2;
-
-    fn get_kind(&self) -> NodeKind {
-        match self {
-            // operands
-            Expr::Number(_) => NodeKind::Operand,
-            Expr::Scoped(_) => NodeKind::Operand,
-
-            // unary
-            Expr::Pos => NodeKind::Unary,
-            Expr::Neg => NodeKind::Unary,
-
-            // binary
-            Expr::Add => NodeKind::Binary,
-            Expr::Sub => NodeKind::Binary,
-            Expr::Mul => NodeKind::Binary,
-            Expr::Div => NodeKind::Binary,
-        }
-    }
-
-    fn get_precedence(&self) -> Precedence {
-        match self {
-            // operands
-            Expr::Number(_) => unreachable!(),
-            Expr::Scoped(_) => unreachable!(),
-
-            // unary
-            Expr::Pos => 2,
-            Expr::Neg => 2,
-
-            // binary
-            Expr::Mul => 1,
-            Expr::Div => 1,
-            Expr::Add => 0,
-            Expr::Sub => 0,
-        }
-    }
-}
-
 /// Parses an iterator of tokens
 pub fn parse(
     tokens: &mut SpannedIter<Token>,
@@ -89,6 +55,8 @@ pub fn parse(
 }
 
 /// Parses an expr from a iterator of tokens
+///
+/// Parentheses are parsed inline as groups, so the whole expr (parentheses included) is a single ASA
 pub fn parse_expr(
     first_tok: NextTok,
     tokens: &mut SpannedIter<Token>,
@@ -96,115 +64,85 @@ pub fn parse_expr(
 ) -> Result<NextTokWith<VectorASA<Spanned<Expr>>>, Error> {
     let mut asa = VectorASA::new(Expr::MAX_PRECEDENCE);
     let start_span = first_tok.as_ref().map(|Spanned { span, .. }| span.clone());
+    let mut end_span = start_span.clone();
+
+    // the spans of the opening parentheses that haven't been closed yet
+    let mut parens = Vec::new();
 
-    // iterate through all the tokens and parse each of them
+    // iterate through all the tokens and parse each of them (until a token that the parser doesn't recognise)
     let mut current_tok = first_tok;
-    while let Some(Spanned { item: token, span }) = current_tok {
+    let next_tok = loop {
+        let Some(Spanned { item: token, span }) = current_tok else {
+            break None;
+        };
+
         // parse the current token
         match token {
             // operands
-            Token::Number(num) => parse::operand(Spanned::new(Expr::Number(num), span), &mut asa)?,
-            Token::LParen => parse_paren(span, tokens, &mut asa)?,
+            Token::Number(num) => parse::node(Spanned::new(Expr::Number(num), span.clone()), &mut asa)?,
+
+            // parentheses (groups)
+            Token::LParen => {
+                parse::open_group(&mut asa).map_err(|_| Error::UnexpectedCharacter(span.clone()))?;
+                parens.push(span.clone());
+            },
+            Token::RParen if !parens.is_empty() => close_paren(parens.pop().unwrap(), &span, &mut asa)?,
 
             // unary left-aligned nodes (only if the ASA is incomplete)
-            Token::Plus if !*asa.is_complete() => parse::unary_left_align(Spanned::new(Expr::Pos, span), &mut asa)?,
-            Token::Dash if !*asa.is_complete() => parse::unary_left_align(Spanned::new(Expr::Neg, span), &mut asa)?,
+            Token::Plus if !*asa.is_complete() => parse::node(Spanned::new(Expr::Pos, span.clone()), &mut asa)?,
+            Token::Dash if !*asa.is_complete() => parse::node(Spanned::new(Expr::Neg, span.clone()), &mut asa)?,
 
             // binary nodes
-            Token::Plus => parse::binary_node(Spanned::new(Expr::Add, span), true, &mut asa)?,
-            Token::Dash => parse::binary_node(Spanned::new(Expr::Sub, span), true, &mut asa)?,
-            Token::Star => parse::binary_node(Spanned::new(Expr::Mul, span), true, &mut asa)?,
-            Token::Slash => parse::binary_node(Spanned::new(Expr::Div, span), true, &mut asa)?,
-
-            // tokens that the parser doesn't recognise
-            _ => {
-                // in this case, we should make sure the ASA is valid and then return the unknown token alongside the spanned ASA
-                parse::ensure_completed(&mut asa)?;
-                let expr_span = Span {
-                    filename: filename.to_string(),
-                    range: start_span.unwrap().range.start..asa.get_node(asa.get_len()-1).span.range.end,
-                };
-
-                return Ok(NextTokWith {
-                    item: Spanned::new(asa, expr_span.clone()),
-                    next_tok: Some(Spanned::new(token, span)),
-                })
-            },
+            Token::Plus => parse::node(Spanned::new(Expr::Add, span.clone()), &mut asa)?,
+            Token::Dash => parse::node(Spanned::new(Expr::Sub, span.clone()), &mut asa)?,
+            Token::Star => parse::node(Spanned::new(Expr::Mul, span.clone()), &mut asa)?,
+            Token::Slash => parse::node(Spanned::new(Expr::Div, span.clone()), &mut asa)?,
+
+            // tokens that the parser doesn't recognise (including unmatched closing parentheses) are returned alongside the spanned ASA
+            _ => break Some(Spanned::new(token, span)),
         }
 
-        // update the current token
+        // update the current and last parsed token
+        end_span = Some(span);
         current_tok = token::next_token(filename, tokens)?;
+    };
+
+    // make sure the innermost parentheses are closed (after making sure their contents are valid)
+    if let Some(start_span) = parens.pop() {
+        close_paren(start_span.clone(), &start_span, &mut asa)?;
+        return Err(Error::UnclosedParen {
+            start_span,
+            expected_span: match next_tok {
+                Some(Spanned { span, .. }) => span,
+                None => Span { filename: filename.to_string(), range: tokens.span() },
+            },
+        });
     }
 
-    // ensure that the ASA is valid and completed and then return the expr with no next token
+    // ensure that the ASA is valid and completed and then return the expr alongside the next token
     parse::ensure_completed(&mut asa)?;
     let span = Span {
         filename: filename.to_string(),
-        range: start_span.unwrap().range.start..asa.get_node(asa.get_len()-1).span.range.end,
+        range: start_span.unwrap().range.start..end_span.unwrap().range.end,
     };
     Ok(NextTokWith {
         item: Spanned::new(asa, span),
-        next_tok: None,
+        next_tok,
     })
 }
 
-/// Parses parentheses (encapsulated expressions) (assuming the opening '(' token has been consumed already)
-pub fn parse_paren(
+/// Closes the innermost parentheses (group), ending at a span, and checks that they aren't empty
+fn close_paren(
     start_span: Span,
-    tokens: &mut SpannedIter<'_, Token>,
+    end_span: &Span,
     asa: &mut impl ASA<Node = Spanned<Expr>>,
 ) -> Result<(), Error> {
-    // check for empty parentheses
-    let next_tok = token::next_token(&start_span.filename, tokens)?;
-    match next_tok {
-        // closing parentheses
-        Some(Spanned { item: Token::RParen, span }) =>{
-            return Err(Error::EmptyParen {
-                span: Span { filename: start_span.filename.clone(), range: start_span.range.start..span.range.end },
-                expected_span: Span { filename: start_span.filename, range: start_span.range.end..start_span.range.end },
-            });
-        },
-        // EOF
-        None => {
-            return Err(Error::EmptyParen {
-                expected_span: Span { filename: start_span.filename.clone(), range: start_span.range.end..start_span.range.end },
-                span: start_span,
-            });
-        },
-        // expr (okay)
-        _ => (),
-    }
-    
-    // parse the internal expr
-    let NextTokWith { item: expr, next_tok } = parse_expr(next_tok, tokens, &start_span.filename)?;
-
-    // parse for the closing ')' character
-    let end_span = match next_tok {
-        // make sure the parentheses are closed
-        Some(Spanned { item: Token::RParen, span }) => span,
-
-        // expected closing parenthesis
-        None => return Err(Error::UnclosedParen {
-            expected_span: Span { filename: start_span.filename.clone(), range: tokens.span() },
-            start_span,
-        }),
-
-        // expected closing parenthesis instead
-        Some(Spanned { span, .. }) => return Err(Error::UnclosedParen {
-            start_span,
-            expected_span: span,
+    match parse::close_group(asa) {
+        // empty parentheses
+        Err(KError::ExpectedNode(None)) => Err(Error::EmptyParen {
+            expected_span: Span { filename: start_span.filename.clone(), range: start_span.range.end..start_span.range.end },
+            span: Span { filename: start_span.filename, range: start_span.range.start..end_span.range.end },
         }),
-    };
-
-    // append the succesfully parsed parentheses (scope)
-    parse::operand(
-        Spanned::new(
-            Expr::Scoped(Box::new(expr)),
-            Span {
-                filename: start_span.filename,
-                range: start_span.range.start..end_span.range.end
-            }
-        ),
-        asa,
-    ).map_err(|err| err.into())
+        result => result.map_err(|err| err.into()),
+    }
 }
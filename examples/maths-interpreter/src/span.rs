@@ -61,4 +61,9 @@ impl<T: Debug + Clone + Node> Node for Spanned<T> {
     fn get_precedence(&self) -> ketchup::Precedence {
         self.item.get_precedence()
     }
+
+    #[inline]
+    fn get_associativity(&self) -> ketchup::prelude::Associativity {
+        self.item.get_associativity()
+    }
 }
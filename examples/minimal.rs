@@ -1,58 +1,33 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use ketchup::node::Node;
+
+// define the precedence and kind of each node
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+#[allow(dead_code)] // not every node is used in this demo
 enum Expr {
+    // operands are independant nodes that don't require any other nodes to be 'complete'
+    #[operand]
     Number(i32),
+
+    // precedence helps determine the order in which nodes get 'evaluated';
+    // the larger the precedence, the 'earlier' it will get 'evaluated'
+
+    // binary nodes are nodes that require two other nodes to be 'complete'
+    #[binary(prec = 0)]
     Add,
+    #[binary(prec = 0)]
     Sub,
+    #[binary(prec = 1)]
     Mul,
+    #[binary(prec = 1)]
     Div,
+
+    // unary nodes are nodes that require another extra node to be 'complete'
+    #[unary(prec = 2, align = left)]
     Neg,
+    #[unary(prec = 2, align = left)]
     Pos,
 }
 
-// define the precedence and kind of the node
-impl ketchup::node::Node for Expr {
-    const MAX_PRECEDENCE: ketchup::Precedence = 2;
-
-    fn get_kind(&self) -> ketchup::node::NodeKind {
-        use ketchup::node::NodeKind;
-
-        match self {
-            // operands are independant nodes that don't require any other nodes to be 'complete'
-            Expr::Number(_) => NodeKind::Operand,
-            // Unary nodes are nodes that require another extra node to be 'complete'
-            Expr::Pos => NodeKind::Unary,
-            Expr::Neg => NodeKind::Unary,
-            // Binary nodes are nodes that require two other nodes to be 'complete'
-            Expr::Add => NodeKind::Binary,
-            Expr::Sub => NodeKind::Binary,
-            Expr::Mul => NodeKind::Binary,
-            Expr::Div => NodeKind::Binary,
-        }
-    }
-
-    fn get_precedence(&self) -> ketchup::Precedence {
-        match self {
-            // precedence helps determine the order in which nodes get 'evaluated';
-            // the larger the precedence, the 'earlier' it will get 'evaluated'
-            //
-            // it is not possible to query the precedence of an operand and it won't ever happen unless ketchup has a critical bug so it's arlight to just panic
-
-            // operands
-            Expr::Number(_) => unreachable!(),
-
-            // unary nodes (left-align)
-            Expr::Pos => 2,
-            Expr::Neg => 2,
-
-            // binary nodes
-            Expr::Mul => 1,
-            Expr::Div => 1,
-            Expr::Add => 0,
-            Expr::Sub => 0,
-        }
-    }
-}
-
 // that's it, that's all you have to do to setup the ketchup parser
 
 fn main() {
@@ -63,33 +38,38 @@ fn main() {
 
     // parse a number
     let number = Expr::Number(12);
-    parse::operand(number, &mut asa).unwrap(); // shouldn't throw any errors
+    parse::node(number, &mut asa).unwrap(); // shouldn't throw any errors
 
     // parse an add node
-    parse::binary_node(Expr::Add, true, &mut asa).unwrap();
+    parse::node(Expr::Add, &mut asa).unwrap();
 
     // parse another number
     let number = Expr::Number(4);
-    parse::operand(number, &mut asa).unwrap();
+    parse::node(number, &mut asa).unwrap();
 
     // parse a multiply node
-    parse::binary_node(Expr::Mul, true, &mut asa).unwrap();
+    parse::node(Expr::Mul, &mut asa).unwrap();
 
     // parse another number
     let number = Expr::Number(8);
-    parse::operand(number, &mut asa).unwrap();
+    parse::node(number, &mut asa).unwrap();
 
     // verify everything is working
     assert!(*asa.is_complete()); // verify that the ASA is not missing any expected nodes
     assert_eq!( // verify that the abstract syntax array is correct *(if it wasn't this wouldn't be a very good library would it?)*
         asa.vector[..],
-        [ // the array is structured really weirdly to help you visualise the structure of the ASA as a tree
-                                 Expr::Add,
-            Expr::Number(12),               Expr::Mul,
-                                 Expr::Number(4), Expr::Number(8),
-        ],
+        [Expr::Add, Expr::Number(12), Expr::Mul, Expr::Number(4), Expr::Number(8)],
     );
 
+    // the ASA is a tree flattened into an array, which is easier to see when it's rendered as one
+    assert_eq!(format!("{:?}", render::sexpr(&asa)), "(Add Number(12) (Mul Number(4) Number(8)))");
+    println!("{asa:#?}");
+    // Add
+    // ├── Number(12)
+    // └── Mul
+    //     ├── Number(4)
+    //     └── Number(8)
+
     // so, this isn't very practical *by itself*, but when paired with a lexer (such as logos), a fancy error reporting system such as ariadne, and some hand-written parsing (like for function definitions), ketchup becomes an extremely flexible and powerful parser that's fit for any project
     //
     // if you want to see more of ketchup's power, then check the crates in `examples` directory
@@ -0,0 +1,397 @@
+//! Derive macros for the [ketchup](https://docs.rs/ketchup) parser
+
+#![warn(missing_docs)]
+
+use std::collections::BTreeSet;
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{meta::ParseNestedMeta, parse_macro_input, spanned::Spanned, Data, DeriveInput, Field, Ident, LitBool, LitInt, Member, Meta, Token, Type, TypePath, Variant};
+
+/// Derives `ketchup::node::Node` for an enum of nodes
+///
+/// Every variant must be annotated with exactly one of the following attributes:
+/// - `#[operand]` for terminal nodes
+/// - `#[unary(prec = 2, align = left)]` for unary nodes (`align` is `left` or `right` and defaults to `left`)
+/// - `#[binary(prec = 1, assoc = left)]` for binary nodes (`assoc` is `left` or `right` and defaults to `left`)
+/// - `#[mixfix(prec = 0, assoc = right, leading = true, holes = 1)]` for mixfix nodes (`leading` defaults to `true` and `holes` defaults to `1`, which makes a ternary node like `a ? b : c`)
+/// - `#[nary(allow_trailing = true)]` for n-ary nodes, which store their arity in their first field (a `usize`) (`allow_trailing` defaults to `false`)
+/// - `#[postfix(prec = 3, assoc = left, allow_trailing = true)]` for postfix nodes with arguments (eg. calls and indexing), which also store their arity (the amount of arguments) in their first field
+///
+/// Unary nodes may also take an `assoc` argument, which only matters for right-aligned ones.
+///
+/// Precedence values can be any integer (eg. copied from a language spec, or spaced out by 10 to leave room for new levels), and may be negative or have gaps in them;
+/// they're remapped at compile time to dense indexes in the same order, so `get_precedence` returns the index of the node's precedence among the ones used and `MAX_PRECEDENCE` is the largest index
+///
+/// ```
+/// use ketchup::node::Node;
+///
+/// #[derive(Debug, Clone, Node)]
+/// enum Expr {
+///     #[operand]
+///     Number(i32),
+///     #[unary(prec = 2, align = left)]
+///     Neg,
+///     #[binary(prec = 1)]
+///     Mul,
+///     #[binary(prec = 0, assoc = left)]
+///     Add,
+/// }
+///
+/// assert_eq!(Expr::MAX_PRECEDENCE, 2);
+/// assert_eq!(Expr::Mul.get_precedence(), 1);
+/// ```
+///
+/// ```
+/// use ketchup::node::Node;
+///
+/// #[derive(Debug, Clone, Node)]
+/// enum Expr {
+///     #[operand]
+///     Number(i32),
+///     #[unary(prec = 20)]
+///     Neg,
+///     #[binary(prec = 10)] // precedences 11..=19 are left free for later
+///     Mul,
+///     #[binary(prec = -5)]
+///     Add,
+/// }
+///
+/// assert_eq!(Expr::MAX_PRECEDENCE, 2);
+/// assert_eq!(Expr::Add.get_precedence(), 0);
+/// assert_eq!(Expr::Mul.get_precedence(), 1);
+/// ```
+///
+/// The arity of n-ary and postfix nodes must be stored as a `usize`
+///
+/// ```compile_fail
+/// use ketchup::node::Node;
+///
+/// #[derive(Debug, Clone, Node)]
+/// enum Expr {
+///     #[operand]
+///     Number(i32),
+///     #[nary]
+///     Tuple(u8), // error: n-ary nodes require their first field to be a `usize` to store their arity in
+/// }
+/// ```
+#[proc_macro_derive(Node, attributes(operand, unary, binary, mixfix, nary, postfix))]
+pub fn derive_node(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The alignment or associativity of a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// The kind of node a variant is annotated as
+#[derive(Clone)]
+enum Kind {
+    Operand,
+    Unary {
+        prec: i128,
+        align: Side,
+        assoc: Side,
+    },
+    Binary {
+        prec: i128,
+        assoc: Side,
+    },
+    Mixfix {
+        prec: i128,
+        assoc: Side,
+        leading: bool,
+        holes: usize,
+    },
+    Nary {
+        allow_trailing: bool,
+        /// The field of the variant that stores the arity
+        arity: Member,
+    },
+    Postfix {
+        prec: i128,
+        assoc: Side,
+        allow_trailing: bool,
+        /// The field of the variant that stores the arity
+        arity: Member,
+    },
+}
+
+impl Kind {
+    /// The associativity of the node kind (operands are left-associative)
+    fn associativity(&self) -> Side {
+        match self {
+            Kind::Operand | Kind::Nary { .. } => Side::Left,
+            Kind::Unary { assoc, .. } => *assoc,
+            Kind::Binary { assoc, .. } => *assoc,
+            Kind::Mixfix { assoc, .. } => *assoc,
+            Kind::Postfix { assoc, .. } => *assoc,
+        }
+    }
+
+    /// The precedence of the node kind (operands have none)
+    fn precedence(&self) -> Option<i128> {
+        match self {
+            Kind::Operand | Kind::Nary { .. } => None,
+            Kind::Unary { prec, .. } => Some(*prec),
+            Kind::Binary { prec, .. } => Some(*prec),
+            Kind::Mixfix { prec, .. } => Some(*prec),
+            Kind::Postfix { prec, .. } => Some(*prec),
+        }
+    }
+}
+
+/// Generates the `Node` implementation for a derive input
+fn derive(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(Span::call_site(), "`Node` can only be derived for enums"));
+    };
+
+    // parse the kind of each of the variants
+    let mut variants = Vec::with_capacity(data.variants.len());
+    for variant in data.variants.iter() {
+        let kind = parse_kind(variant)?;
+        variants.push((&variant.ident, kind, variant.span()));
+    }
+
+    // remap the (possibly sparse) precedences to dense indexes, in order
+    let precedences = variants.iter()
+        .filter_map(|(_, kind, _)| kind.precedence())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let dense = |prec: i128| precedences.binary_search(&prec).expect("every precedence is in the set of precedences");
+    let max_precedence = precedences.len().saturating_sub(1);
+
+    // generate the match arms
+    let kind_arms = variants.iter().map(|(ident, kind, _)| {
+        let kind = match kind {
+            Kind::Operand => quote! { ::ketchup::node::NodeKind::Operand },
+            Kind::Unary { align: Side::Left, .. } => quote! { ::ketchup::node::NodeKind::Unary(::ketchup::node::Alignment::Left) },
+            Kind::Unary { align: Side::Right, .. } => quote! { ::ketchup::node::NodeKind::Unary(::ketchup::node::Alignment::Right) },
+            Kind::Binary { .. } => quote! { ::ketchup::node::NodeKind::Binary },
+            Kind::Mixfix { leading, holes, .. } => quote! {
+                ::ketchup::node::NodeKind::Mixfix(::ketchup::node::Mixfix { leading: #leading, holes: #holes })
+            },
+            Kind::Nary { allow_trailing, arity } => return quote! {
+                Self::#ident { #arity: arity, .. } => ::ketchup::node::NodeKind::Nary(::ketchup::node::Nary { arity: *arity, allow_trailing: #allow_trailing }),
+            },
+            Kind::Postfix { allow_trailing, arity, .. } => return quote! {
+                Self::#ident { #arity: arity, .. } => ::ketchup::node::NodeKind::Postfix(::ketchup::node::Nary { arity: *arity, allow_trailing: #allow_trailing }),
+            },
+        };
+        quote! { Self::#ident { .. } => #kind, }
+    });
+    let arity_arms = variants.iter().map(|(ident, kind, _)| match kind {
+        Kind::Nary { arity, .. } | Kind::Postfix { arity, .. } => quote! { Self::#ident { #arity: arity, .. } => *arity = new_arity, },
+        _ => quote! { Self::#ident { .. } => unreachable!("only n-ary and postfix nodes have an arity"), },
+    });
+    let has_nary = variants.iter().any(|(_, kind, _)| matches!(kind, Kind::Nary { .. } | Kind::Postfix { .. }));
+    let set_arity = has_nary.then(|| quote! {
+        #[inline]
+        fn set_arity(&mut self, new_arity: usize) {
+            match self {
+                #(#arity_arms)*
+            }
+        }
+    });
+    let precedence_arms = variants.iter().map(|(ident, kind, _)| match kind.precedence().map(dense) {
+        Some(prec) => quote! { Self::#ident { .. } => #prec, },
+        None => quote! { Self::#ident { .. } => unreachable!("operands do not have a precedence"), },
+    });
+    let associativity_arms = variants.iter().map(|(ident, kind, _)| {
+        let assoc = match kind.associativity() {
+            Side::Left => quote! { ::ketchup::node::Associativity::Left },
+            Side::Right => quote! { ::ketchup::node::Associativity::Right },
+        };
+        quote! { Self::#ident { .. } => #assoc, }
+    });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::ketchup::node::Node for #name #ty_generics #where_clause {
+            const MAX_PRECEDENCE: ::ketchup::Precedence = #max_precedence;
+
+            #[inline]
+            fn get_kind(&self) -> ::ketchup::node::NodeKind {
+                match self {
+                    #(#kind_arms)*
+                }
+            }
+
+            #[inline]
+            fn get_precedence(&self) -> ::ketchup::Precedence {
+                match self {
+                    #(#precedence_arms)*
+                }
+            }
+
+            #[inline]
+            fn get_associativity(&self) -> ::ketchup::node::Associativity {
+                match self {
+                    #(#associativity_arms)*
+                }
+            }
+
+            #set_arity
+        }
+    })
+}
+
+/// Parses the node kind attribute of a variant
+fn parse_kind(variant: &Variant) -> syn::Result<Kind> {
+    let mut kind = None;
+
+    for attr in variant.attrs.iter() {
+        let parsed = if attr.path().is_ident("operand") {
+            attr.meta.require_path_only()?;
+            Kind::Operand
+        } else if attr.path().is_ident("unary") {
+            let mut prec = None;
+            let mut align = Side::Left;
+            let mut assoc = Side::Left;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prec") {
+                    prec = Some(parse_prec(&meta)?);
+                } else if meta.path.is_ident("align") {
+                    align = parse_side(&meta.value()?.parse()?)?;
+                } else if meta.path.is_ident("assoc") {
+                    assoc = parse_side(&meta.value()?.parse()?)?;
+                } else {
+                    return Err(meta.error("expected `prec`, `align` or `assoc`"));
+                }
+                Ok(())
+            })?;
+            let prec = prec.ok_or_else(|| syn::Error::new(attr.span(), "unary nodes require a precedence (`prec = ..`)"))?;
+            Kind::Unary { prec, align, assoc }
+        } else if attr.path().is_ident("binary") {
+            let mut prec = None;
+            let mut assoc = Side::Left;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prec") {
+                    prec = Some(parse_prec(&meta)?);
+                } else if meta.path.is_ident("assoc") {
+                    assoc = parse_side(&meta.value()?.parse()?)?;
+                } else {
+                    return Err(meta.error("expected `prec` or `assoc`"));
+                }
+                Ok(())
+            })?;
+            let prec = prec.ok_or_else(|| syn::Error::new(attr.span(), "binary nodes require a precedence (`prec = ..`)"))?;
+            Kind::Binary { prec, assoc }
+        } else if attr.path().is_ident("mixfix") {
+            let mut prec = None;
+            let mut assoc = Side::Left;
+            let mut leading = true;
+            let mut holes = 1;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prec") {
+                    prec = Some(parse_prec(&meta)?);
+                } else if meta.path.is_ident("assoc") {
+                    assoc = parse_side(&meta.value()?.parse()?)?;
+                } else if meta.path.is_ident("leading") {
+                    leading = meta.value()?.parse::<LitBool>()?.value;
+                } else if meta.path.is_ident("holes") {
+                    let lit = meta.value()?.parse::<LitInt>()?;
+                    holes = lit.base10_parse()?;
+                    if holes == 0 {
+                        return Err(syn::Error::new(lit.span(), "mixfix nodes require at least one hole"));
+                    }
+                } else {
+                    return Err(meta.error("expected `prec`, `assoc`, `leading` or `holes`"));
+                }
+                Ok(())
+            })?;
+            let prec = prec.ok_or_else(|| syn::Error::new(attr.span(), "mixfix nodes require a precedence (`prec = ..`)"))?;
+            Kind::Mixfix { prec, assoc, leading, holes }
+        } else if attr.path().is_ident("nary") {
+            let mut allow_trailing = false;
+            if !matches!(attr.meta, Meta::Path(_)) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("allow_trailing") {
+                        allow_trailing = meta.value()?.parse::<LitBool>()?.value;
+                    } else {
+                        return Err(meta.error("expected `allow_trailing`"));
+                    }
+                    Ok(())
+                })?;
+            }
+
+            let arity = arity_field(variant, "n-ary")?;
+            Kind::Nary { allow_trailing, arity }
+        } else if attr.path().is_ident("postfix") {
+            let mut prec = None;
+            let mut assoc = Side::Left;
+            let mut allow_trailing = false;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prec") {
+                    prec = Some(parse_prec(&meta)?);
+                } else if meta.path.is_ident("assoc") {
+                    assoc = parse_side(&meta.value()?.parse()?)?;
+                } else if meta.path.is_ident("allow_trailing") {
+                    allow_trailing = meta.value()?.parse::<LitBool>()?.value;
+                } else {
+                    return Err(meta.error("expected `prec`, `assoc` or `allow_trailing`"));
+                }
+                Ok(())
+            })?;
+            let prec = prec.ok_or_else(|| syn::Error::new(attr.span(), "postfix nodes require a precedence (`prec = ..`)"))?;
+            let arity = arity_field(variant, "postfix")?;
+            Kind::Postfix { prec, assoc, allow_trailing, arity }
+        } else {
+            continue;
+        };
+
+        // make sure there is only one kind per node
+        if kind.is_some() {
+            return Err(syn::Error::new(attr.span(), "a node can only have one kind"));
+        }
+        kind = Some(parsed);
+    }
+
+    kind.ok_or_else(|| syn::Error::new(variant.ident.span(), "missing node kind; expected `#[operand]`, `#[unary(..)]`, `#[binary(..)]`, `#[mixfix(..)]`, `#[nary]` or `#[postfix(..)]`"))
+}
+
+/// Returns the field that an n-ary or postfix node stores it's arity in (the first field of the variant, which must be written as `usize`)
+fn arity_field(variant: &Variant, kind: &str) -> syn::Result<Member> {
+    let error = |span| syn::Error::new(span, format!("{kind} nodes require their first field to be a `usize` to store their arity in"));
+
+    let field = variant.fields.iter().next().ok_or_else(|| error(variant.span()))?;
+    let is_usize = matches!(&field.ty, Type::Path(TypePath { qself: None, path }) if path.segments.last().is_some_and(|segment| segment.ident == "usize" && segment.arguments.is_none()));
+    if !is_usize {
+        return Err(error(field.ty.span()));
+    }
+
+    Ok(match field {
+        Field { ident: Some(ident), .. } => Member::Named(ident.clone()),
+        _ => Member::Unnamed(0.into()),
+    })
+}
+
+/// Parses the value of a `prec = ..` argument, which may be any (possibly negative) integer
+fn parse_prec(meta: &ParseNestedMeta) -> syn::Result<i128> {
+    let value = meta.value()?;
+    let negative = value.parse::<Option<Token![-]>>()?.is_some();
+    let prec = value.parse::<LitInt>()?.base10_parse::<i128>()?;
+    Ok(if negative { -prec } else { prec })
+}
+
+/// Parses either `left` or `right`
+fn parse_side(ident: &Ident) -> syn::Result<Side> {
+    if ident == "left" {
+        Ok(Side::Left)
+    } else if ident == "right" {
+        Ok(Side::Right)
+    } else {
+        Err(syn::Error::new(ident.span(), "expected either `left` or `right`"))
+    }
+}
@@ -1,6 +1,12 @@
 //! Traits for implementing an ASA
 
-use crate::{node, Precedence};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc, vec, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::fmt::{self, Debug, Formatter};
+#[cfg(feature = "alloc")]
+use crate::render;
+use crate::{node::{self, NodeKind}, Precedence};
 
 /// An Abstract Syntax Array
 /// 
@@ -16,17 +22,43 @@ pub trait ASA {
 
     /// Queries a node in the ASA (panic on out-of-bounds index)
     fn get_node(&self, idx: usize) -> &Self::Node;
+    /// Queries a mutable node in the ASA (panic on out-of-bounds index)
+    fn get_node_mut(&mut self, idx: usize) -> &mut Self::Node;
     /// Queries the length of the ASA
     fn get_len(&self) -> usize;
 
-    /// Pushes a node to the end of the ASA
+    /// Queries the kind of a node in the ASA (panic on out-of-bounds index)
+    #[inline]
+    fn get_kind(&self, idx: usize) -> NodeKind {
+        node::Node::get_kind(self.get_node(idx))
+    }
+
+    /// Queries the precedence of a node in the ASA (panic on out-of-bounds index)
+    #[inline]
+    fn get_precedence(&self, idx: usize) -> Precedence {
+        node::Node::get_precedence(self.get_node(idx))
+    }
+
+    /// Sets the arity of an n-ary node in the ASA (panic on out-of-bounds index)
+    #[inline]
+    fn set_arity(&mut self, idx: usize, arity: usize) {
+        node::Node::set_arity(self.get_node_mut(idx), arity);
+    }
+
+    /// Pushes a node to the end of the ASA (panic if the ASA is full)
     fn push(&mut self, node: Self::Node);
-    /// PUshes a node to the start of the ASA
+    /// PUshes a node to the start of the ASA (panic if the ASA is full)
     fn push_start(&mut self, node: Self::Node);
 
-    /// Inserts a node into an index in the ASA (panic on out-of-bounds)
+    /// Inserts a node into an index in the ASA (panic on out-of-bounds or if the ASA is full)
     fn insert(&mut self, idx: usize, node: Self::Node);
 
+    /// Returns if the ASA has no room left for another node (only for fixed-capacity ASAs)
+    #[inline]
+    fn is_full(&self) -> bool {
+        false
+    }
+
     /// Returns a mutable pointer to the `completed` flag/field of the ASA
     fn is_complete(&mut self) -> &mut bool;
 
@@ -36,31 +68,203 @@ pub trait ASA {
     /// Returns a mutable pointer to the `last_incomplete` field (index in the ASA)
     fn last_incomplete(&mut self) -> &mut Option<usize>;
 
+    /// Returns a mutable pointer to the `last_operand` field (index in the ASA of the start of the last operand)
+    fn last_operand(&mut self) -> &mut usize;
+
     /// Returns a mutable pointer to the precedence index lookup-table array
     fn lookuptable(&mut self) -> &mut [Option<usize>];
+
+    /// Returns a mutable pointer to the innermost open scope (if there is one)
+    fn scope(&mut self) -> Option<&mut Scope>;
+
+    /// Opens a new innermost scope for the mixfix or n-ary node at an index (or for a group starting at it), saving the lookup-table onto the stack of scopes and then clearing it (panic if the stack of scopes is full)
+    fn open_scope(&mut self, node: usize);
+
+    /// Closes the innermost scope, restoring the lookup-table from before it was opened (panic if there are no open scopes)
+    fn close_scope(&mut self);
+
+    /// Returns if the stack of scopes has no room left for another scope (only for fixed-capacity ASAs)
+    #[inline]
+    fn scopes_full(&self) -> bool {
+        false
+    }
 }
 
-/// An implementation of ASA that uses an underlying vector
+/// An ASA that nodes can be removed from (or replaced in), which is only needed to roll back a [`Checkpointed`](crate::checkpoint::Checkpointed) ASA (as parsing itself never removes nodes)
+pub trait Removable: ASA {
+    /// Removes a node from an index in the ASA, shifting everything after it back (panic on out-of-bounds)
+    fn remove(&mut self, idx: usize) -> Self::Node;
+
+    /// Replaces a node at an index in the ASA with another one, returning the old node (panic on out-of-bounds)
+    ///
+    /// Unlike writing through `get_node_mut`, anything the ASA caches about the node (eg. the kind of it in a `SoaASA`) is updated too
+    #[inline]
+    fn replace(&mut self, idx: usize, node: Self::Node) -> Self::Node {
+        core::mem::replace(self.get_node_mut(idx), node)
+    }
+}
+
+/// A delimited part of the ASA that is currently being parsed separately from the rest of it (eg. the hole of a mixfix node, a slot of an n-ary node or a group)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scope {
+    /// The index of the mixfix or n-ary node that owns the scope (or the index that a group starts at)
+    pub node: usize,
+    /// Which of the node's holes (or slots) is being filled (starting from zero)
+    pub hole: usize,
+    /// If the scope is a group (eg. parentheses) instead of a hole of a node (see [`parse::open_group`](crate::parse::open_group))
+    pub group: bool,
+}
+
+/// An open scope alongside the precedence index lookup-table from before it was opened
+#[cfg(feature = "alloc")]
 #[derive(Debug, Clone)]
-pub struct VectorASA<Node: node::Node> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct SavedScope {
+    scope: Scope,
+    lookuptable: Box<[Option<usize>]>,
+}
+
+/// The parsing state shared by the allocating implementations of ASA, which only differ in how they store their nodes
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+struct State {
     is_complete: bool,
     last_incomplete: Option<usize>,
+    last_operand: usize,
     max_precedence: Precedence,
     precedence_jumptable: Box<[Option<usize>]>,
+    scopes: Vec<SavedScope>,
+}
+
+#[cfg(feature = "alloc")]
+impl State {
+    /// Creates the parsing state of an empty ASA
+    ///
+    /// Panics on `Precedence::MAX`, which is the `MAX_PRECEDENCE` of nodes that only know their precedences at runtime (eg. [`Dynamic`](crate::table::Dynamic) nodes)
+    #[inline]
+    fn new(max_precedence: Precedence) -> Self {
+        assert!(
+            max_precedence < Precedence::MAX,
+            "the maximum precedence of these nodes is only known at runtime, so the ASA must be created with it (eg. through `OperatorTable::asa`, `Tree::into_asa_with` or `parse::collect_with`)",
+        );
+
+        Self {
+            is_complete: false,
+            last_incomplete: None,
+            last_operand: 0,
+            max_precedence,
+            precedence_jumptable: vec![None; max_precedence + 1].into_boxed_slice(),
+            scopes: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn scope(&mut self) -> Option<&mut Scope> {
+        self.scopes.last_mut().map(|saved| &mut saved.scope)
+    }
+
+    #[inline]
+    fn open_scope(&mut self, node: usize) {
+        let lookuptable = self.precedence_jumptable.clone();
+        self.precedence_jumptable.fill(None);
+        self.scopes.push(SavedScope { scope: Scope { node, hole: 0, group: false }, lookuptable });
+    }
+
+    #[inline]
+    fn close_scope(&mut self) {
+        let saved = self.scopes.pop().expect("there are no open scopes to close");
+        self.precedence_jumptable = saved.lookuptable;
+    }
+
+    /// Formats the parsing state of an ASA as the struct `ty`, with it's nodes as the field `field`
+    fn fmt<A: ASA>(&self, asa: &A, f: &mut Formatter<'_>, ty: &str, field: &str, nodes: &dyn Debug) -> fmt::Result {
+        // pretty-print completed ASAs as a tree, as the flat array is hard to read
+        if f.alternate() && self.is_complete && self.scopes.is_empty() {
+            return write!(f, "{:?}", render::tree(asa));
+        }
+
+        f.debug_struct(ty)
+            .field("is_complete", &self.is_complete)
+            .field("last_incomplete", &self.last_incomplete)
+            .field("last_operand", &self.last_operand)
+            .field("max_precedence", &self.max_precedence)
+            .field("precedence_jumptable", &self.precedence_jumptable)
+            .field("scopes", &self.scopes)
+            .field(field, nodes)
+            .finish()
+    }
+}
+
+/// Implements the methods of ASA that only access the parsing state, for the implementations of ASA with a `state: State` field
+#[cfg(feature = "alloc")]
+macro_rules! state_methods {
+    () => {
+        #[inline]
+        fn is_complete(&mut self) -> &mut bool {
+            &mut self.state.is_complete
+        }
+
+        #[inline]
+        fn last_incomplete(&mut self) -> &mut Option<usize> {
+            &mut self.state.last_incomplete
+        }
+
+        #[inline]
+        fn last_operand(&mut self) -> &mut usize {
+            &mut self.state.last_operand
+        }
+
+        #[inline]
+        fn max_precedence(&self) -> Precedence {
+            self.state.max_precedence
+        }
+
+        #[inline]
+        fn lookuptable(&mut self) -> &mut [Option<usize>] {
+            &mut self.state.precedence_jumptable
+        }
+
+        #[inline]
+        fn scope(&mut self) -> Option<&mut Scope> {
+            self.state.scope()
+        }
+
+        #[inline]
+        fn open_scope(&mut self, node: usize) {
+            self.state.open_scope(node);
+        }
+
+        #[inline]
+        fn close_scope(&mut self) {
+            self.state.close_scope();
+        }
+    };
+}
+
+/// An implementation of ASA that uses an underlying vector
+///
+/// Pretty-printing a completed `VectorASA` (`{:#?}`) renders it as a tree
+///
+/// With the `serde` feature, the whole state of the ASA is (de)serialized, so that partially parsed ASAs can be resumed
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "Node: serde::Deserialize<'de>"), try_from = "serde_impl::VectorASAData<Node>"))]
+pub struct VectorASA<Node: node::Node> {
+    state: State,
     /// The internal vector
     pub vector: Vec<Node>,
 }
 
+#[cfg(feature = "alloc")]
 impl<Node: node::Node> ASA for VectorASA<Node> {
     type Node = Node;
 
     #[inline]
     fn new(max_precedence: Precedence) -> Self {
         Self {
-            is_complete: false,
-            last_incomplete: None,
-            max_precedence,
-            precedence_jumptable: vec![None; max_precedence+1].into_boxed_slice(),
+            state: State::new(max_precedence),
             vector: Vec::new(),
         }
     }
@@ -75,6 +279,11 @@ impl<Node: node::Node> ASA for VectorASA<Node> {
         &self.vector[idx]
     }
 
+    #[inline]
+    fn get_node_mut(&mut self, idx: usize) -> &mut Self::Node {
+        &mut self.vector[idx]
+    }
+
     #[inline]
     fn push(&mut self, node: Self::Node) {
         self.vector.push(node);
@@ -90,6 +299,655 @@ impl<Node: node::Node> ASA for VectorASA<Node> {
         self.vector.insert(idx, node);
     }
 
+    state_methods!();
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> Removable for VectorASA<Node> {
+    #[inline]
+    fn remove(&mut self, idx: usize) -> Self::Node {
+        self.vector.remove(idx)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> Debug for VectorASA<Node> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.state.fmt(self, f, "VectorASA", "vector", &self.vector)
+    }
+}
+
+/// An implementation of ASA that uses an underlying double-ended queue
+///
+/// Inserting a node only shifts the nodes on whichever side of it is shorter, so pushing to the start (and inserting near the start, like low-precedence binary nodes do) is cheap, unlike with `VectorASA`
+///
+/// Pretty-printing a completed `DequeASA` (`{:#?}`) renders it as a tree
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct DequeASA<Node: node::Node> {
+    state: State,
+    /// The internal double-ended queue
+    pub deque: VecDeque<Node>,
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> ASA for DequeASA<Node> {
+    type Node = Node;
+
+    #[inline]
+    fn new(max_precedence: Precedence) -> Self {
+        Self {
+            state: State::new(max_precedence),
+            deque: VecDeque::new(),
+        }
+    }
+
+    #[inline]
+    fn get_len(&self) -> usize {
+        self.deque.len()
+    }
+
+    #[inline]
+    fn get_node(&self, idx: usize) -> &Self::Node {
+        &self.deque[idx]
+    }
+
+    #[inline]
+    fn get_node_mut(&mut self, idx: usize) -> &mut Self::Node {
+        &mut self.deque[idx]
+    }
+
+    #[inline]
+    fn push(&mut self, node: Self::Node) {
+        self.deque.push_back(node);
+    }
+
+    #[inline]
+    fn push_start(&mut self, node: Self::Node) {
+        self.deque.push_front(node);
+    }
+
+    #[inline]
+    fn insert(&mut self, idx: usize, node: Self::Node) {
+        self.deque.insert(idx, node);
+    }
+
+    state_methods!();
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> Removable for DequeASA<Node> {
+    #[inline]
+    fn remove(&mut self, idx: usize) -> Self::Node {
+        self.deque.remove(idx).expect("index out of bounds of the ASA")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> Debug for DequeASA<Node> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.state.fmt(self, f, "DequeASA", "deque", &self.deque)
+    }
+}
+
+/// The maximum amount of nodes in a leaf chunk (and of children in a branch chunk) of a `RopeASA`
+#[cfg(feature = "alloc")]
+const ROPE_CHUNK: usize = 64;
+
+/// A chunk of a `RopeASA`; either a leaf of nodes, or a branch of child chunks alongside their lengths
+///
+/// Child chunks are shared between forks of a rope, and are only cloned once they're written to (copy-on-write)
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+enum Chunk<Node> {
+    Leaf(Vec<Node>),
+    Branch(Vec<(usize, Arc<Chunk<Node>>)>),
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: Clone> Chunk<Node> {
+    /// Finds the child of a branch that contains an index, returning it's position and the index relative to it
+    #[inline]
+    fn locate(children: &[(usize, Arc<Chunk<Node>>)], mut idx: usize) -> (usize, usize) {
+        for (pos, (len, _)) in children.iter().enumerate() {
+            if idx < *len {
+                return (pos, idx);
+            }
+            idx -= len;
+        }
+        panic!("index out of bounds of the ASA");
+    }
+
+    fn get(&self, idx: usize) -> &Node {
+        match self {
+            Chunk::Leaf(nodes) => &nodes[idx],
+            Chunk::Branch(children) => {
+                let (pos, idx) = Self::locate(children, idx);
+                children[pos].1.get(idx)
+            },
+        }
+    }
+
+    fn get_mut(&mut self, idx: usize) -> &mut Node {
+        match self {
+            Chunk::Leaf(nodes) => &mut nodes[idx],
+            Chunk::Branch(children) => {
+                let (pos, idx) = Self::locate(children, idx);
+                Arc::make_mut(&mut children[pos].1).get_mut(idx)
+            },
+        }
+    }
+
+    /// Inserts a node into an index of the chunk, returning the (length and) second half of the chunk if it had to be split
+    fn insert(&mut self, mut idx: usize, node: Node) -> Option<(usize, Arc<Chunk<Node>>)> {
+        match self {
+            Chunk::Leaf(nodes) => {
+                nodes.insert(idx, node);
+                if nodes.len() <= ROPE_CHUNK {
+                    return None;
+                }
+
+                let split = nodes.split_off(nodes.len() / 2);
+                Some((split.len(), Arc::new(Chunk::Leaf(split))))
+            },
+            Chunk::Branch(children) => {
+                // find the child to insert into (inserting at the very end goes into the last child)
+                let mut pos = 0;
+                while pos < children.len() - 1 && idx >= children[pos].0 {
+                    idx -= children[pos].0;
+                    pos += 1;
+                }
+
+                let (len, child) = &mut children[pos];
+                *len += 1;
+                let (split_len, split) = Arc::make_mut(child).insert(idx, node)?;
+                *len -= split_len;
+                children.insert(pos + 1, (split_len, split));
+                if children.len() <= ROPE_CHUNK {
+                    return None;
+                }
+
+                let split = children.split_off(children.len() / 2);
+                Some((split.iter().map(|(len, _)| len).sum(), Arc::new(Chunk::Branch(split))))
+            },
+        }
+    }
+
+    /// Removes a node from an index of the chunk (dropping any child chunks that become empty)
+    fn remove(&mut self, idx: usize) -> Node {
+        match self {
+            Chunk::Leaf(nodes) => nodes.remove(idx),
+            Chunk::Branch(children) => {
+                let (pos, idx) = Self::locate(children, idx);
+                let (len, child) = &mut children[pos];
+                *len -= 1;
+                let node = Arc::make_mut(child).remove(idx);
+                if *len == 0 && children.len() > 1 {
+                    children.remove(pos);
+                }
+                node
+            },
+        }
+    }
+
+    /// Appends all of the nodes of the chunk (in order) onto a vector, only cloning the nodes of chunks that are still shared with a fork
+    fn append_to(self, vector: &mut Vec<Node>) {
+        match self {
+            Chunk::Leaf(mut nodes) => vector.append(&mut nodes),
+            Chunk::Branch(children) => children.into_iter().for_each(|(_, child)| Arc::unwrap_or_clone(child).append_to(vector)),
+        }
+    }
+}
+
+/// An implementation of ASA that uses an underlying rope (a B-tree of chunks of nodes)
+///
+/// Inserting and indexing are `O(log n)` no matter where in the ASA they are, so inputs that insert many nodes into the middle of a large ASA aren't quadratic, at the cost of slower indexing overall;
+/// once parsing is done, it can be converted into a contiguous `VectorASA` (or `Vec`)
+///
+/// The chunks of the rope are copy-on-write, so a partially built `RopeASA` can be forked (see [`RopeASA::fork`]) without copying it
+///
+/// Pretty-printing a completed `RopeASA` (`{:#?}`) renders it as a tree
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct RopeASA<Node: node::Node> {
+    state: State,
+    rope: Chunk<Node>,
+    len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> RopeASA<Node> {
+    /// Forks the ASA into a second branch of parsing, for keeping more than one interpretation of the input alive at once (eg. for a token that could be either a postfix or a binary node)
+    ///
+    /// Both branches share the nodes already parsed, and each has it's own parsing state (`is_complete`, `last_incomplete`, `last_operand`, lookup-table and scopes);
+    /// only the parsing state is copied, and a branch only copies the chunks of the rope that it writes to (`O(log n)` of them for each node parsed)
+    #[inline]
+    pub fn fork(&self) -> Self {
+        self.clone()
+    }
+
+    /// Converts the rope into a contiguous vector of the nodes
+    #[inline]
+    pub fn into_vec(self) -> Vec<Node> {
+        let mut vector = Vec::with_capacity(self.len);
+        self.rope.append_to(&mut vector);
+        vector
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> ASA for RopeASA<Node> {
+    type Node = Node;
+
+    #[inline]
+    fn new(max_precedence: Precedence) -> Self {
+        Self {
+            state: State::new(max_precedence),
+            rope: Chunk::Leaf(Vec::new()),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn get_len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn get_node(&self, idx: usize) -> &Self::Node {
+        assert!(idx < self.len, "index out of bounds of the ASA");
+        self.rope.get(idx)
+    }
+
+    #[inline]
+    fn get_node_mut(&mut self, idx: usize) -> &mut Self::Node {
+        assert!(idx < self.len, "index out of bounds of the ASA");
+        self.rope.get_mut(idx)
+    }
+
+    #[inline]
+    fn push(&mut self, node: Self::Node) {
+        self.insert(self.len, node);
+    }
+
+    #[inline]
+    fn push_start(&mut self, node: Self::Node) {
+        self.insert(0, node);
+    }
+
+    fn insert(&mut self, idx: usize, node: Self::Node) {
+        assert!(idx <= self.len, "index out of bounds of the ASA");
+        self.len += 1;
+
+        // if the root had to be split, then grow the rope by a level
+        if let Some((split_len, split)) = self.rope.insert(idx, node) {
+            let rope = core::mem::replace(&mut self.rope, Chunk::Branch(Vec::new()));
+            self.rope = Chunk::Branch(vec![(self.len - split_len, Arc::new(rope)), (split_len, split)]);
+        }
+    }
+
+    state_methods!();
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> Removable for RopeASA<Node> {
+    #[inline]
+    fn remove(&mut self, idx: usize) -> Self::Node {
+        assert!(idx < self.len, "index out of bounds of the ASA");
+        self.len -= 1;
+        self.rope.remove(idx)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> Debug for RopeASA<Node> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // the chunks are an implementation detail, so only the nodes themselves are shown
+        struct Nodes<'a, Node: node::Node>(&'a RopeASA<Node>);
+        impl<Node: node::Node> Debug for Nodes<'_, Node> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries((0..self.0.len).map(|idx| self.0.get_node(idx))).finish()
+            }
+        }
+
+        self.state.fmt(self, f, "RopeASA", "nodes", &Nodes(self))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> From<RopeASA<Node>> for VectorASA<Node> {
+    /// Converts the rope into a contiguous `VectorASA` in the same parsing state (so that more nodes can still be parsed onto it)
+    #[inline]
+    fn from(asa: RopeASA<Node>) -> Self {
+        let RopeASA { state, rope, len } = asa;
+
+        let mut vector = Vec::with_capacity(len);
+        rope.append_to(&mut vector);
+
+        Self { state, vector }
+    }
+}
+
+/// The cached kind and precedence of a node in a `SoaASA`, alongside where it's payload is stored
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy)]
+struct Slot {
+    kind: NodeKind,
+    /// Operands and n-ary nodes don't have a precedence
+    precedence: Option<Precedence>,
+    payload: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl Slot {
+    /// Caches the kind and precedence of a node whose payload is stored at `payload`
+    #[inline]
+    fn new<Node: node::Node>(node: &Node, payload: usize) -> Self {
+        let kind = node.get_kind();
+        let precedence = match kind {
+            NodeKind::Operand | NodeKind::Nary(_) => None,
+            _ => Some(node.get_precedence()),
+        };
+        Self { kind, precedence, payload }
+    }
+}
+
+/// A struct-of-arrays implementation of ASA that keeps the nodes themselves (payloads) separate from their order in the ASA
+///
+/// The ASA is an array of small fixed-size slots, each caching the kind and precedence of a node alongside the index of it's payload;
+/// payloads are only ever pushed, so inserting a node only shifts the slots instead of whole (possibly large) nodes, and querying the kind or precedence of a node (eg. when walking the tree) never touches the payload
+///
+/// Removing a node leaves a hole in the payloads (instead of moving another payload into it's place), which is reclaimed once every payload after it is removed too, like when rolling back (see [`Checkpointed`](crate::checkpoint::Checkpointed))
+///
+/// The cached kind of a node is only updated through `ASA::set_arity` and `Removable::replace`, so modifying the kind (or precedence) of a node through `get_node_mut` is **not** reflected in the ASA
+///
+/// Pretty-printing a completed `SoaASA` (`{:#?}`) renders it as a tree
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct SoaASA<Node: node::Node> {
+    state: State,
+    slots: Vec<Slot>,
+    payloads: Vec<Option<Node>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> ASA for SoaASA<Node> {
+    type Node = Node;
+
+    #[inline]
+    fn new(max_precedence: Precedence) -> Self {
+        Self {
+            state: State::new(max_precedence),
+            slots: Vec::new(),
+            payloads: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn get_len(&self) -> usize {
+        self.slots.len()
+    }
+
+    #[inline]
+    fn get_node(&self, idx: usize) -> &Self::Node {
+        self.payloads[self.slots[idx].payload].as_ref().expect("slots only point to payloads that weren't removed")
+    }
+
+    #[inline]
+    fn get_node_mut(&mut self, idx: usize) -> &mut Self::Node {
+        self.payloads[self.slots[idx].payload].as_mut().expect("slots only point to payloads that weren't removed")
+    }
+
+    #[inline]
+    fn get_kind(&self, idx: usize) -> NodeKind {
+        self.slots[idx].kind
+    }
+
+    #[inline]
+    fn get_precedence(&self, idx: usize) -> Precedence {
+        self.slots[idx].precedence.expect("operands and n-ary nodes do not have a precedence")
+    }
+
+    #[inline]
+    fn set_arity(&mut self, idx: usize, arity: usize) {
+        let kind = {
+            let payload = self.get_node_mut(idx);
+            node::Node::set_arity(payload, arity);
+            node::Node::get_kind(payload)
+        };
+        self.slots[idx].kind = kind;
+    }
+
+    #[inline]
+    fn push(&mut self, node: Self::Node) {
+        self.insert(self.slots.len(), node);
+    }
+
+    #[inline]
+    fn push_start(&mut self, node: Self::Node) {
+        self.insert(0, node);
+    }
+
+    #[inline]
+    fn insert(&mut self, idx: usize, node: Self::Node) {
+        self.slots.insert(idx, Slot::new(&node, self.payloads.len()));
+        self.payloads.push(Some(node));
+    }
+
+    state_methods!();
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> Removable for SoaASA<Node> {
+    fn remove(&mut self, idx: usize) -> Self::Node {
+        let slot = self.slots.remove(idx);
+        let node = self.payloads[slot.payload].take().expect("slots only point to payloads that weren't removed");
+
+        // reclaim the holes at the end of the payloads (usually just the removed one, as the latest node is usually the first to be removed)
+        while let Some(None) = self.payloads.last() {
+            self.payloads.pop();
+        }
+        node
+    }
+
+    #[inline]
+    fn replace(&mut self, idx: usize, node: Self::Node) -> Self::Node {
+        let payload = self.slots[idx].payload;
+        self.slots[idx] = Slot::new(&node, payload);
+        self.payloads[payload].replace(node).expect("slots only point to payloads that weren't removed")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> Debug for SoaASA<Node> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // the slots are an implementation detail, so only the nodes themselves (in order) are shown
+        struct Nodes<'a, Node: node::Node>(&'a SoaASA<Node>);
+        impl<Node: node::Node> Debug for Nodes<'_, Node> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries((0..self.0.slots.len()).map(|idx| self.0.get_node(idx))).finish()
+            }
+        }
+
+        self.state.fmt(self, f, "SoaASA", "nodes", &Nodes(self))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Node: node::Node> From<SoaASA<Node>> for VectorASA<Node> {
+    /// Reorders the payloads into a contiguous `VectorASA` in the same parsing state (so that more nodes can still be parsed onto it)
+    fn from(asa: SoaASA<Node>) -> Self {
+        let SoaASA { state, slots, mut payloads } = asa;
+        let vector = slots.iter().map(|slot| payloads[slot.payload].take().unwrap()).collect();
+        Self { state, vector }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use alloc::{boxed::Box, format, string::String, vec::Vec};
+    use serde::ser::{Serialize, SerializeStruct, Serializer};
+    use super::{SavedScope, State, VectorASA};
+    use crate::{node, Precedence};
+
+    /// Serializes the parsing state alongside the nodes as one flat struct (the same fields as `VectorASAData`)
+    impl<Node: node::Node + Serialize> Serialize for VectorASA<Node> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut asa = serializer.serialize_struct("VectorASA", 7)?;
+            asa.serialize_field("is_complete", &self.state.is_complete)?;
+            asa.serialize_field("last_incomplete", &self.state.last_incomplete)?;
+            asa.serialize_field("last_operand", &self.state.last_operand)?;
+            asa.serialize_field("max_precedence", &self.state.max_precedence)?;
+            asa.serialize_field("precedence_jumptable", &self.state.precedence_jumptable)?;
+            asa.serialize_field("scopes", &self.state.scopes)?;
+            asa.serialize_field("vector", &self.vector)?;
+            asa.end()
+        }
+    }
+
+    /// The unvalidated fields of a deserialized `VectorASA`
+    #[derive(serde::Deserialize)]
+    pub struct VectorASAData<Node> {
+        is_complete: bool,
+        last_incomplete: Option<usize>,
+        last_operand: usize,
+        max_precedence: Precedence,
+        precedence_jumptable: Box<[Option<usize>]>,
+        scopes: Vec<SavedScope>,
+        vector: Vec<Node>,
+    }
+
+    impl<Node: node::Node> TryFrom<VectorASAData<Node>> for VectorASA<Node> {
+        type Error = String;
+
+        fn try_from(data: VectorASAData<Node>) -> Result<Self, Self::Error> {
+            // every lookup-table must have an entry for every precedence
+            let tables = core::iter::once(&data.precedence_jumptable).chain(data.scopes.iter().map(|scope| &scope.lookuptable));
+            for table in tables {
+                if table.len() != data.max_precedence + 1 {
+                    return Err(format!("lookup-table has {} entries, but the max precedence is {}", table.len(), data.max_precedence));
+                }
+            }
+
+            // every index must point into the ASA, except for the start of a group, which may be where the next node will be pushed (eg. in `1 + (`)
+            let len = data.vector.len();
+            let indexes = data.precedence_jumptable.iter().flatten()
+                .chain(data.last_incomplete.iter())
+                .chain(data.scopes.iter().flat_map(|scope| scope.lookuptable.iter().flatten()))
+                .map(|idx| (*idx, len))
+                .chain(data.scopes.iter().map(|scope| (scope.scope.node, if scope.scope.group { len + 1 } else { len })));
+            for (idx, bound) in indexes {
+                if idx >= bound {
+                    return Err(format!("index {idx} is out of bounds of an ASA of length {len}"));
+                }
+            }
+
+            Ok(Self {
+                state: State {
+                    is_complete: data.is_complete,
+                    last_incomplete: data.last_incomplete,
+                    last_operand: data.last_operand,
+                    max_precedence: data.max_precedence,
+                    precedence_jumptable: data.precedence_jumptable,
+                    scopes: data.scopes,
+                },
+                vector: data.vector,
+            })
+        }
+    }
+}
+
+/// A fixed-capacity implementation of ASA that uses underlying arrays, and so doesn't need an allocator
+///
+/// - `N` is the maximum amount of nodes in the ASA
+/// - `P` is the size of the precedence index lookup-table, which **must** be `Node::MAX_PRECEDENCE + 1` (checked at compile-time);
+///   this is a temporary limitation, as the size can't be derived from `Node` until generic const expressions (`[_; Node::MAX_PRECEDENCE + 1]`) are stabilised
+/// - `S` is the maximum amount of nested mixfix or n-ary scopes that can be open at once (zero by default)
+///
+/// When the ASA is full, parsing a node gives a 'capacity exceeded' error instead of panicking
+#[derive(Debug, Clone)]
+pub struct ArrayASA<Node: node::Node, const N: usize, const P: usize, const S: usize = 0> {
+    is_complete: bool,
+    last_incomplete: Option<usize>,
+    last_operand: usize,
+    precedence_jumptable: [Option<usize>; P],
+    scopes: [(Scope, [Option<usize>; P]); S],
+    scopes_len: usize,
+    nodes: [Option<Node>; N],
+    len: usize,
+}
+
+impl<Node: node::Node, const N: usize, const P: usize, const S: usize> ArrayASA<Node, N, P, S> {
+    /// Returns the nodes in the ASA
+    #[inline]
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes[..self.len].iter().map(|node| node.as_ref().unwrap())
+    }
+}
+
+impl<Node: node::Node, const N: usize, const P: usize, const S: usize> ASA for ArrayASA<Node, N, P, S> {
+    type Node = Node;
+
+    #[inline]
+    fn new(max_precedence: Precedence) -> Self {
+        const { assert!(P == Node::MAX_PRECEDENCE + 1, "the lookup-table size of an `ArrayASA` must be `Node::MAX_PRECEDENCE + 1`") };
+        assert_eq!(max_precedence + 1, P, "the lookup-table size of an `ArrayASA` must be `max_precedence + 1`");
+
+        Self {
+            is_complete: false,
+            last_incomplete: None,
+            last_operand: 0,
+            precedence_jumptable: [None; P],
+            scopes: [(Scope { node: 0, hole: 0, group: false }, [None; P]); S],
+            scopes_len: 0,
+            nodes: core::array::from_fn(|_| None),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn get_len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn get_node(&self, idx: usize) -> &Self::Node {
+        assert!(idx < self.len, "index out of bounds of the ASA");
+        self.nodes[idx].as_ref().unwrap()
+    }
+
+    #[inline]
+    fn get_node_mut(&mut self, idx: usize) -> &mut Self::Node {
+        assert!(idx < self.len, "index out of bounds of the ASA");
+        self.nodes[idx].as_mut().unwrap()
+    }
+
+    #[inline]
+    fn push(&mut self, node: Self::Node) {
+        self.insert(self.len, node);
+    }
+
+    #[inline]
+    fn push_start(&mut self, node: Self::Node) {
+        self.insert(0, node);
+    }
+
+    #[inline]
+    fn insert(&mut self, idx: usize, node: Self::Node) {
+        assert!(idx <= self.len, "index out of bounds of the ASA");
+        assert!(!self.is_full(), "the ASA is full");
+
+        // shift the nodes after the index along by one (moving the empty slot at the end into the index)
+        self.nodes[idx..=self.len].rotate_right(1);
+        self.nodes[idx] = Some(node);
+        self.len += 1;
+    }
+
+    #[inline]
+    fn is_full(&self) -> bool {
+        self.len == N
+    }
+
     #[inline]
     fn is_complete(&mut self) -> &mut bool {
         &mut self.is_complete
@@ -100,13 +958,59 @@ impl<Node: node::Node> ASA for VectorASA<Node> {
         &mut self.last_incomplete
     }
 
+    #[inline]
+    fn last_operand(&mut self) -> &mut usize {
+        &mut self.last_operand
+    }
+
     #[inline]
     fn max_precedence(&self) -> Precedence {
-        self.max_precedence
+        P - 1
     }
 
     #[inline]
     fn lookuptable(&mut self) -> &mut [Option<usize>] {
         &mut self.precedence_jumptable
     }
+
+    #[inline]
+    fn scope(&mut self) -> Option<&mut Scope> {
+        let idx = self.scopes_len.checked_sub(1)?;
+        Some(&mut self.scopes[idx].0)
+    }
+
+    #[inline]
+    fn open_scope(&mut self, node: usize) {
+        assert!(!self.scopes_full(), "the stack of scopes is full");
+
+        self.scopes[self.scopes_len] = (Scope { node, hole: 0, group: false }, self.precedence_jumptable);
+        self.scopes_len += 1;
+        self.precedence_jumptable = [None; P];
+    }
+
+    #[inline]
+    fn close_scope(&mut self) {
+        assert!(self.scopes_len > 0, "there are no open scopes to close");
+
+        self.scopes_len -= 1;
+        self.precedence_jumptable = self.scopes[self.scopes_len].1;
+    }
+
+    #[inline]
+    fn scopes_full(&self) -> bool {
+        self.scopes_len == S
+    }
+}
+
+impl<Node: node::Node, const N: usize, const P: usize, const S: usize> Removable for ArrayASA<Node, N, P, S> {
+    #[inline]
+    fn remove(&mut self, idx: usize) -> Self::Node {
+        assert!(idx < self.len, "index out of bounds of the ASA");
+
+        // shift the nodes after the index back by one (moving the removed node's now empty slot to the end)
+        let node = self.nodes[idx].take().unwrap();
+        self.nodes[idx..self.len].rotate_left(1);
+        self.len -= 1;
+        node
+    }
 }
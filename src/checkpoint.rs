@@ -0,0 +1,281 @@
+//! Checkpoints of the parsing state of an ASA, for backtracking
+//!
+//! Rolling back doesn't clone the ASA; instead, every change made to the ASA after a checkpoint is recorded in an undo log, which is undone in reverse
+//!
+//! The parsing state (the flags, the lookup-table and the innermost scope) is only recorded the first time that it's accessed after a checkpoint (or after a change of scope, for the lookup-table and scope), as only it's earliest state needs to be restored;
+//! so the undo log grows with the amount of nodes changed rather than with the amount of accesses or the size of the lookup-table
+
+use alloc::{boxed::Box, vec::Vec};
+use crate::{asa::{Removable, Scope, ASA}, node::NodeKind, Precedence};
+
+/// A point in the parsing of a [`Checkpointed`] ASA that it can be rolled back to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint(usize);
+
+/// A change made to the ASA, alongside what's needed to undo it
+#[derive(Debug, Clone)]
+enum Undo<N> {
+    Inserted(usize),
+    Modified(usize, N),
+    Complete(bool),
+    LastIncomplete(Option<usize>),
+    LastOperand(usize),
+    Lookuptable(Box<[Option<usize>]>),
+    Scope(Scope),
+    OpenedScope,
+    ClosedScope(Scope, Box<[Option<usize>]>),
+}
+
+/// An ASA wrapper that can take checkpoints of the ASA and roll back to them
+///
+/// Changes are only recorded once a checkpoint has been taken, and keep being recorded until they're committed (see [`Checkpointed::commit`]);
+/// as the parsing state is handed out through mutable pointers, the first access to each part of it after a checkpoint is recorded, so the undo log should be committed once backtracking is no longer possible
+///
+/// Rolling back removes the nodes inserted since the checkpoint, so the wrapped ASA must be [`Removable`]
+#[derive(Debug, Clone)]
+pub struct Checkpointed<A: Removable> {
+    asa: A,
+    log: Vec<Undo<A::Node>>,
+    recording: bool,
+    recorded: Recorded,
+}
+
+/// Which parts of the parsing state have already been recorded since the last checkpoint (or change of scope, for the lookup-table and scope)
+#[derive(Debug, Clone, Copy, Default)]
+struct Recorded {
+    complete: bool,
+    last_incomplete: bool,
+    last_operand: bool,
+    lookuptable: bool,
+    scope: bool,
+}
+
+impl<A: Removable> Checkpointed<A> {
+    /// Wraps an ASA (which can already be partially parsed)
+    #[inline]
+    pub fn wrap(asa: A) -> Self {
+        Self { asa, log: Vec::new(), recording: false, recorded: Recorded::default() }
+    }
+
+    /// Unwraps the ASA, discarding any checkpoints
+    #[inline]
+    pub fn into_inner(self) -> A {
+        self.asa
+    }
+
+    /// Returns the wrapped ASA
+    #[inline]
+    pub fn inner(&self) -> &A {
+        &self.asa
+    }
+
+    /// Takes a checkpoint of the current parsing state of the ASA, and starts recording changes to it
+    #[inline]
+    pub fn checkpoint(&mut self) -> Checkpoint {
+        self.recording = true;
+        self.recorded = Recorded::default();
+        Checkpoint(self.log.len())
+    }
+
+    /// Rolls the ASA back to the parsing state it was in when a checkpoint was taken (panics if the checkpoint was committed or already rolled back past)
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        assert!(checkpoint.0 <= self.log.len(), "cannot roll back to a checkpoint that was committed or already rolled back past");
+
+        for undo in self.log.drain(checkpoint.0..).rev() {
+            match undo {
+                Undo::Inserted(idx) => { self.asa.remove(idx); },
+                Undo::Modified(idx, node) => { self.asa.replace(idx, node); },
+                Undo::Complete(is_complete) => *self.asa.is_complete() = is_complete,
+                Undo::LastIncomplete(last_incomplete) => *self.asa.last_incomplete() = last_incomplete,
+                Undo::LastOperand(last_operand) => *self.asa.last_operand() = last_operand,
+                Undo::Lookuptable(lookuptable) => self.asa.lookuptable().copy_from_slice(&lookuptable),
+                Undo::Scope(scope) => *self.asa.scope().unwrap() = scope,
+                Undo::OpenedScope => self.asa.close_scope(),
+                Undo::ClosedScope(scope, lookuptable) => {
+                    // reopen the scope over the lookup-table it restored, and then put back the lookup-table from inside of it
+                    self.asa.open_scope(scope.node);
+                    *self.asa.scope().unwrap() = scope;
+                    self.asa.lookuptable().copy_from_slice(&lookuptable);
+                },
+            }
+        }
+        self.recorded = Recorded::default();
+    }
+
+    /// Stops recording changes and forgets every checkpoint, so the ASA can no longer be rolled back
+    #[inline]
+    pub fn commit(&mut self) {
+        self.log.clear();
+        self.recording = false;
+        self.recorded = Recorded::default();
+    }
+
+    /// Returns the amount of changes recorded in the undo log since the first checkpoint that wasn't committed
+    #[inline]
+    pub fn recorded(&self) -> usize {
+        self.log.len()
+    }
+
+    /// Records a change to the ASA (if there are any checkpoints to roll back to)
+    #[inline]
+    fn record(&mut self, undo: impl FnOnce(&mut A) -> Undo<A::Node>) {
+        if self.recording {
+            let undo = undo(&mut self.asa);
+            self.log.push(undo);
+        }
+    }
+
+    /// Records a part of the parsing state, unless it was already recorded (see [`Recorded`])
+    #[inline]
+    fn record_once(&mut self, recorded: fn(&mut Recorded) -> &mut bool, undo: impl FnOnce(&mut A) -> Undo<A::Node>) {
+        if self.recording && !*recorded(&mut self.recorded) {
+            *recorded(&mut self.recorded) = true;
+            self.record(undo);
+        }
+    }
+
+    /// Starts recording the lookup-table and the innermost scope again after a change of scope, as the ones recorded before belong to a different scope
+    #[inline]
+    fn change_scope(&mut self) {
+        self.recorded.lookuptable = false;
+        self.recorded.scope = false;
+    }
+}
+
+impl<A: Removable> ASA for Checkpointed<A> {
+    type Node = A::Node;
+
+    #[inline]
+    fn new(max_precedence: Precedence) -> Self {
+        Self::wrap(A::new(max_precedence))
+    }
+
+    #[inline]
+    fn get_node(&self, idx: usize) -> &Self::Node {
+        self.asa.get_node(idx)
+    }
+
+    #[inline]
+    fn get_node_mut(&mut self, idx: usize) -> &mut Self::Node {
+        self.record(|asa| Undo::Modified(idx, asa.get_node(idx).clone()));
+        self.asa.get_node_mut(idx)
+    }
+
+    #[inline]
+    fn get_len(&self) -> usize {
+        self.asa.get_len()
+    }
+
+    #[inline]
+    fn get_kind(&self, idx: usize) -> NodeKind {
+        self.asa.get_kind(idx)
+    }
+
+    #[inline]
+    fn get_precedence(&self, idx: usize) -> Precedence {
+        self.asa.get_precedence(idx)
+    }
+
+    #[inline]
+    fn set_arity(&mut self, idx: usize, arity: usize) {
+        self.record(|asa| Undo::Modified(idx, asa.get_node(idx).clone()));
+        self.asa.set_arity(idx, arity);
+    }
+
+    #[inline]
+    fn push(&mut self, node: Self::Node) {
+        self.record(|asa| Undo::Inserted(asa.get_len()));
+        self.asa.push(node);
+    }
+
+    #[inline]
+    fn push_start(&mut self, node: Self::Node) {
+        self.record(|_| Undo::Inserted(0));
+        self.asa.push_start(node);
+    }
+
+    #[inline]
+    fn insert(&mut self, idx: usize, node: Self::Node) {
+        self.record(|_| Undo::Inserted(idx));
+        self.asa.insert(idx, node);
+    }
+
+    #[inline]
+    fn is_full(&self) -> bool {
+        self.asa.is_full()
+    }
+
+    #[inline]
+    fn is_complete(&mut self) -> &mut bool {
+        self.record_once(|recorded| &mut recorded.complete, |asa| Undo::Complete(*asa.is_complete()));
+        self.asa.is_complete()
+    }
+
+    #[inline]
+    fn max_precedence(&self) -> Precedence {
+        self.asa.max_precedence()
+    }
+
+    #[inline]
+    fn last_incomplete(&mut self) -> &mut Option<usize> {
+        self.record_once(|recorded| &mut recorded.last_incomplete, |asa| Undo::LastIncomplete(*asa.last_incomplete()));
+        self.asa.last_incomplete()
+    }
+
+    #[inline]
+    fn last_operand(&mut self) -> &mut usize {
+        self.record_once(|recorded| &mut recorded.last_operand, |asa| Undo::LastOperand(*asa.last_operand()));
+        self.asa.last_operand()
+    }
+
+    #[inline]
+    fn lookuptable(&mut self) -> &mut [Option<usize>] {
+        self.record_once(|recorded| &mut recorded.lookuptable, |asa| Undo::Lookuptable(Box::from(&*asa.lookuptable())));
+        self.asa.lookuptable()
+    }
+
+    #[inline]
+    fn scope(&mut self) -> Option<&mut Scope> {
+        if self.asa.scope().is_some() {
+            self.record_once(|recorded| &mut recorded.scope, |asa| Undo::Scope(*asa.scope().unwrap()));
+        }
+        self.asa.scope()
+    }
+
+    #[inline]
+    fn open_scope(&mut self, node: usize) {
+        self.record(|_| Undo::OpenedScope);
+        self.change_scope();
+        self.asa.open_scope(node);
+    }
+
+    #[inline]
+    fn close_scope(&mut self) {
+        self.record(|asa| {
+            let scope = *asa.scope().expect("there are no open scopes to close");
+            Undo::ClosedScope(scope, Box::from(&*asa.lookuptable()))
+        });
+        self.change_scope();
+        self.asa.close_scope();
+    }
+
+    #[inline]
+    fn scopes_full(&self) -> bool {
+        self.asa.scopes_full()
+    }
+}
+
+impl<A: Removable> Removable for Checkpointed<A> {
+    #[inline]
+    fn remove(&mut self, idx: usize) -> Self::Node {
+        assert!(!self.recording, "nodes can't be removed from a `Checkpointed` ASA while there are checkpoints");
+        self.asa.remove(idx)
+    }
+
+    #[inline]
+    fn replace(&mut self, idx: usize, node: Self::Node) -> Self::Node {
+        let old = self.asa.replace(idx, node);
+        self.record(|_| Undo::Modified(idx, old.clone()));
+        old
+    }
+}
@@ -0,0 +1,327 @@
+//! Compact nodes that are just a node type and a payload id, with their kinds coming from a table of operators
+//!
+//! Moving a compact node only moves 8 bytes, so shifting nodes during insertion is cheap no matter how large the values behind them are;
+//! the values (eg. the literals of operands) are kept in a separate payload store (such as an [`Interner`]) and referred to by id
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{fmt::Debug, marker::PhantomData};
+use crate::{node::{Alignment, Associativity, Mixfix, Nary, Node, NodeKind}, DeclaredPrecedence, Precedence};
+
+/// The kind, precedence and associativity of one type of compact node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Operator {
+    /// The kind of node
+    pub kind: NodeKind,
+    /// The precedence of the node as it was declared (ignored for operands and n-ary nodes)
+    ///
+    /// The precedences of a table of [`Operators`] must be dense (see [`dense`]), while an [`OperatorTable`](crate::table::OperatorTable) remaps them itself
+    pub precedence: DeclaredPrecedence,
+    /// The associativity of the node
+    pub associativity: Associativity,
+}
+
+impl Operator {
+    /// An operand
+    #[inline]
+    pub const fn operand() -> Self {
+        Self { kind: NodeKind::Operand, precedence: 0, associativity: Associativity::Left }
+    }
+
+    /// A unary node with an alignment and precedence
+    #[inline]
+    pub const fn unary(align: Alignment, precedence: DeclaredPrecedence) -> Self {
+        Self { kind: NodeKind::Unary(align), precedence, associativity: Associativity::Left }
+    }
+
+    /// A (left-associative) binary node with a precedence
+    #[inline]
+    pub const fn binary(precedence: DeclaredPrecedence) -> Self {
+        Self { kind: NodeKind::Binary, precedence, associativity: Associativity::Left }
+    }
+
+    /// A (left-associative) mixfix node with a layout and precedence
+    #[inline]
+    pub const fn mixfix(mixfix: Mixfix, precedence: DeclaredPrecedence) -> Self {
+        Self { kind: NodeKind::Mixfix(mixfix), precedence, associativity: Associativity::Left }
+    }
+
+    /// An n-ary node
+    #[inline]
+    pub const fn nary(allow_trailing: bool) -> Self {
+        Self { kind: NodeKind::Nary(Nary { arity: 0, allow_trailing }), precedence: 0, associativity: Associativity::Left }
+    }
+
+    /// A (left-associative) postfix node with a precedence
+    #[inline]
+    pub const fn postfix(precedence: DeclaredPrecedence, allow_trailing: bool) -> Self {
+        Self { kind: NodeKind::Postfix(Nary { arity: 0, allow_trailing }), precedence, associativity: Associativity::Left }
+    }
+
+    /// Sets the associativity of the node
+    #[inline]
+    pub const fn assoc(self, associativity: Associativity) -> Self {
+        Self { associativity, ..self }
+    }
+}
+
+/// A table of the operators of a language, indexed by the node type of a [`Compact`] node
+///
+/// ```
+/// use ketchup::{compact::{Operator, Operators}, node::Alignment};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct Maths;
+///
+/// impl Operators for Maths {
+///     const OPERATORS: &'static [Operator] = &[
+///         Operator::operand(), // 0: number
+///         Operator::binary(0), // 1: add
+///         Operator::binary(1), // 2: mul
+///         Operator::unary(Alignment::Left, 2), // 3: neg
+///     ];
+/// }
+/// ```
+///
+/// The precedences of the operators must be dense (in order from `0` with no gaps), which is checked at compile time; sparse precedences can be remapped with [`dense`]
+///
+/// ```compile_fail
+/// use ketchup::{compact::{Compact, Operator, Operators}, node::Node};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct Gaps;
+///
+/// impl Operators for Gaps {
+///     const OPERATORS: &'static [Operator] = &[Operator::operand(), Operator::binary(0), Operator::binary(2)];
+/// }
+///
+/// let _ = Compact::<Gaps>::MAX_PRECEDENCE; // precedence `1` is never used
+/// ```
+pub trait Operators: Debug + Clone + Copy + PartialEq + Eq {
+    /// The operators, indexed by node type
+    const OPERATORS: &'static [Operator];
+}
+
+/// Returns if an operator has a precedence (operands and n-ary nodes don't)
+pub(crate) const fn has_precedence(operator: &Operator) -> bool {
+    !matches!(operator.kind, NodeKind::Operand | NodeKind::Nary(_))
+}
+
+/// Remaps the (possibly sparse or negative) precedences of a table of operators to dense indexes in the same order, so that precedences can be copied from a language spec or spaced out to leave room for new levels
+///
+/// The precedences are sorted once (with an insertion sort, so it's `O(N²)` for `N` operators, which is only ever paid at compile time when used in a `const`) and then each operator's index is binary searched
+///
+/// ```
+/// use ketchup::{compact::{self, Operator, Operators}, node::{Alignment, Node}};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct C;
+///
+/// impl Operators for C {
+///     const OPERATORS: &'static [Operator] = &compact::dense([
+///         Operator::operand(),
+///         Operator::binary(40), // +
+///         Operator::binary(30), // <<
+///         Operator::binary(50), // *
+///         Operator::unary(Alignment::Left, -10), // declared with a negative precedence
+///     ]);
+/// }
+///
+/// assert_eq!(C::OPERATORS[1].precedence, 2);
+/// assert_eq!(C::OPERATORS[4].precedence, 0);
+/// assert_eq!(compact::Compact::<C>::MAX_PRECEDENCE, 3);
+/// ```
+pub const fn dense<const N: usize>(mut operators: [Operator; N]) -> [Operator; N] {
+    // sort a copy of the precedences
+    let mut sorted = [0; N];
+    let mut len = 0;
+    let mut idx = 0;
+    while idx < N {
+        if has_precedence(&operators[idx]) {
+            let precedence = operators[idx].precedence;
+            let mut at = len;
+            while at > 0 && sorted[at - 1] > precedence {
+                sorted[at] = sorted[at - 1];
+                at -= 1;
+            }
+            sorted[at] = precedence;
+            len += 1;
+        }
+        idx += 1;
+    }
+
+    // remove the duplicates, so that the index of a precedence is it's dense precedence
+    let mut distinct = 0;
+    idx = 0;
+    while idx < len {
+        if distinct == 0 || sorted[distinct - 1] != sorted[idx] {
+            sorted[distinct] = sorted[idx];
+            distinct += 1;
+        }
+        idx += 1;
+    }
+
+    idx = 0;
+    while idx < N {
+        if has_precedence(&operators[idx]) {
+            let (mut low, mut high) = (0, distinct);
+            while low < high {
+                let mid = (low + high) / 2;
+                if sorted[mid] < operators[idx].precedence {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            operators[idx].precedence = low as DeclaredPrecedence;
+        }
+        idx += 1;
+    }
+    operators
+}
+
+/// Returns the maximum precedence used by a table of operators, panicking (at compile time) if the precedences aren't dense
+const fn max_precedence(operators: &[Operator]) -> Precedence {
+    let mut max = 0;
+    let mut idx = 0;
+    while idx < operators.len() {
+        let operator = operators[idx];
+        if has_precedence(&operator) {
+            // every precedence must be non-negative and follow another one, so that they're all in order from `0` with no gaps
+            let mut follows = operator.precedence == 0;
+            let mut other = 0;
+            while other < operators.len() && !follows {
+                follows = has_precedence(&operators[other]) && operators[other].precedence == operator.precedence - 1;
+                other += 1;
+            }
+            assert!(operator.precedence >= 0 && follows, "the precedences of compact operators must be dense (in order from `0` with no gaps); remap them with `compact::dense`");
+
+            if operator.precedence as Precedence > max {
+                max = operator.precedence as Precedence;
+            }
+        }
+        idx += 1;
+    }
+    max
+}
+
+/// A compact node; a node type (an index into the table of operators) and a payload id (eg. an id from an [`Interner`])
+///
+/// N-ary and postfix nodes store their arity in place of a payload id
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Compact<O: Operators> {
+    /// The type of the node, which is an index into `O::OPERATORS`
+    pub node_type: u16,
+    /// The id of the payload of the node (or the arity of an n-ary or postfix node)
+    pub payload: u32,
+    operators: PhantomData<O>,
+}
+
+impl<O: Operators> Compact<O> {
+    /// Creates a compact node with a node type and payload id (panics if the node type isn't in the table of operators)
+    #[inline]
+    pub fn new(node_type: u16, payload: u32) -> Self {
+        assert!((node_type as usize) < O::OPERATORS.len(), "node type {node_type} is not in the table of operators");
+        Self { node_type, payload, operators: PhantomData }
+    }
+
+    /// Creates a compact node with a node type and no payload
+    #[inline]
+    pub fn operator(node_type: u16) -> Self {
+        Self::new(node_type, 0)
+    }
+
+    /// Returns the operator of the node's type
+    #[inline]
+    pub fn get_operator(&self) -> &'static Operator {
+        &O::OPERATORS[self.node_type as usize]
+    }
+}
+
+impl<O: Operators> Node for Compact<O> {
+    const MAX_PRECEDENCE: Precedence = max_precedence(O::OPERATORS);
+
+    #[inline]
+    fn get_precedence(&self) -> Precedence {
+        // the precedences are checked to be dense (so they're never negative) by `MAX_PRECEDENCE`
+        self.get_operator().precedence as Precedence
+    }
+
+    #[inline]
+    fn get_kind(&self) -> NodeKind {
+        match self.get_operator().kind {
+            NodeKind::Nary(nary) => NodeKind::Nary(Nary { arity: self.payload as usize, ..nary }),
+            NodeKind::Postfix(args) => NodeKind::Postfix(Nary { arity: self.payload as usize, ..args }),
+            kind => kind,
+        }
+    }
+
+    #[inline]
+    fn get_associativity(&self) -> Associativity {
+        self.get_operator().associativity
+    }
+
+    #[inline]
+    fn set_arity(&mut self, arity: usize) {
+        self.payload = arity.try_into().expect("the arity of a compact n-ary or postfix node must fit in a `u32`");
+    }
+}
+
+/// An ASA of compact nodes
+#[cfg(feature = "alloc")]
+pub type CompactASA<O> = crate::asa::VectorASA<Compact<O>>;
+
+/// A payload store that gives each distinct value a single id, so equal literals share a payload
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct Interner<P: Ord + Clone> {
+    values: Vec<P>,
+    ids: BTreeMap<P, u32>,
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Ord + Clone> Default for Interner<P> {
+    #[inline]
+    fn default() -> Self {
+        Self { values: Vec::new(), ids: BTreeMap::new() }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Ord + Clone> Interner<P> {
+    /// Creates an empty interner
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of a value, storing it if it hasn't been interned before
+    pub fn intern(&mut self, value: P) -> u32 {
+        if let Some(id) = self.ids.get(&value) {
+            return *id;
+        }
+
+        let id = self.values.len().try_into().expect("an interner can't hold more than `u32::MAX` values");
+        self.values.push(value.clone());
+        self.ids.insert(value, id);
+        id
+    }
+
+    /// Queries the value of an id (panics if the id wasn't given out by this interner)
+    #[inline]
+    pub fn get(&self, id: u32) -> &P {
+        &self.values[id as usize]
+    }
+
+    /// Returns the amount of distinct values interned
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns if no values have been interned
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
@@ -0,0 +1,169 @@
+//! Exporting ASAs as [Graphviz](https://graphviz.org) DOT graphs, for visualising how the flat array of an ASA lines up with it's tree
+
+use alloc::{format, string::{String, ToString}, vec::Vec};
+use core::fmt::Write;
+use crate::{asa::{Scope, ASA}, node::{Mixfix, NodeKind}, view::View};
+
+/// Options for exporting an ASA as a DOT graph
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Annotates each node with it's index in the ASA (so the graph lines up with the array)
+    pub indices: bool,
+}
+
+/// Exports a completed ASA (or view) as a DOT graph, with nodes labelled by their `Debug` form and coloured by their kind
+pub fn export<V: View + ?Sized>(view: &V, options: &Options) -> String {
+    let mut dot = String::from("digraph asa {\n");
+    write_nodes(&mut dot, view, None, options, "n", "    ");
+    dot.push_str("}\n");
+    dot
+}
+
+/// A trace of the steps of parsing an ASA, exported as a single DOT graph with one cluster per step
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    options: Options,
+    steps: Vec<String>,
+}
+
+impl Trace {
+    /// Creates an empty trace
+    #[inline]
+    pub fn new(options: Options) -> Self {
+        Self {
+            options,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Records the current state of an ASA (or view) as the next step of the trace (the ASA doesn't have to be completed yet; any missing 'parameters' are just left out)
+    ///
+    /// See [`Trace::step_asa`] for marking the hole that an incomplete ASA is filling
+    #[inline]
+    pub fn step<V: View + ?Sized>(&mut self, label: &str, view: &V) {
+        self.push_step(label, view, None);
+    }
+
+    /// Records the current state of an ASA as the next step of the trace, like [`Trace::step`], but also marks the mixfix, n-ary or postfix node that owns the innermost open scope with the hole (or slot) being filled
+    ///
+    /// As the arity of an n-ary (or postfix) node is only set once it's closed, the nodes in it's slots are drawn after it (as it's siblings) until then
+    #[inline]
+    pub fn step_asa<A: ASA>(&mut self, label: &str, asa: &mut A) {
+        let scope = asa.scope().copied();
+        self.push_step(label, asa, scope);
+    }
+
+    /// Writes a step of the trace as a cluster
+    fn push_step<V: View + ?Sized>(&mut self, label: &str, view: &V, scope: Option<Scope>) {
+        let step = self.steps.len();
+        let mut cluster = String::new();
+        writeln!(cluster, "    subgraph cluster_{step} {{").unwrap();
+        writeln!(cluster, "        label=\"{}\";", escape(label)).unwrap();
+        write_nodes(&mut cluster, view, scope, &self.options, &format!("s{step}_n"), "        ");
+        cluster.push_str("    }\n");
+
+        self.steps.push(cluster);
+    }
+
+    /// Exports the trace as a DOT graph
+    pub fn export(&self) -> String {
+        let mut dot = String::from("digraph trace {\n");
+        for step in self.steps.iter() {
+            dot.push_str(step);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Escapes a string for use inside of a quoted DOT label
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Returns the fill colour of a kind of node
+fn colour(kind: NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Operand => "lightblue",
+        NodeKind::Unary(_) => "khaki",
+        NodeKind::Binary => "lightsalmon",
+        NodeKind::Mixfix(_) => "plum",
+        NodeKind::Nary(_) => "palegreen",
+        NodeKind::Postfix(_) => "lightpink",
+    }
+}
+
+/// Returns the label of the edge from a node to it's child at a position (if it has one)
+fn edge_label(kind: NodeKind, position: usize) -> Option<String> {
+    match kind {
+        NodeKind::Operand | NodeKind::Unary(_) => None,
+        NodeKind::Binary => Some(if position == 0 { "lhs" } else { "rhs" }.to_string()),
+        NodeKind::Mixfix(Mixfix { leading, holes }) => {
+            // the leading 'parameter' (if there is one), then the holes, then the trailing 'parameter'
+            let hole = position.checked_sub(leading as usize);
+            Some(match hole {
+                None => "lhs".to_string(),
+                Some(hole) if hole < holes => format!("hole {hole}"),
+                Some(_) => "rhs".to_string(),
+            })
+        },
+        NodeKind::Nary(_) => Some(position.to_string()),
+        // the operand, then the arguments
+        NodeKind::Postfix(_) => Some(match position.checked_sub(1) {
+            None => "lhs".to_string(),
+            Some(arg) => arg.to_string(),
+        }),
+    }
+}
+
+/// Returns the annotation of a node that owns an open scope (groups don't belong to a node, so they aren't annotated)
+fn open_label(kind: NodeKind, scope: Scope) -> String {
+    match kind {
+        NodeKind::Mixfix(_) => format!(" (open, hole {})", scope.hole),
+        _ => format!(" (open, slot {})", scope.hole),
+    }
+}
+
+/// Writes the nodes and edges of an ASA (with node ids starting with `prefix`, and each line indented by `indent`), marking the node that owns the open scope (if there is one)
+fn write_nodes<V: View + ?Sized>(dot: &mut String, view: &V, scope: Option<Scope>, options: &Options, prefix: &str, indent: &str) {
+    // the ancestors of the current node, alongside their kinds and how many of their children have been written
+    let mut parents: Vec<(usize, NodeKind, usize)> = Vec::new();
+
+    for idx in 0..view.len() {
+        // pop off any parents that have had all of their children written
+        while parents.last().is_some_and(|(_, kind, written)| *written == kind.arity()) {
+            parents.pop();
+        }
+
+        let node = view.node(idx);
+        let kind = view.kind(idx);
+
+        let mut label = if options.indices {
+            format!("{idx}: {node:?}")
+        } else {
+            format!("{node:?}")
+        };
+        let open = scope.filter(|scope| !scope.group && scope.node == idx);
+        if let Some(scope) = open {
+            label.push_str(&open_label(kind, scope));
+        }
+        let style = if open.is_some() { "\"filled,dashed\"" } else { "filled" };
+        writeln!(dot, "{indent}{prefix}{idx} [label=\"{}\", style={style}, fillcolor={}];", escape(&label), colour(kind)).unwrap();
+
+        if let Some((parent, parent_kind, written)) = parents.last_mut() {
+            match edge_label(*parent_kind, *written) {
+                Some(label) => writeln!(dot, "{indent}{prefix}{parent} -> {prefix}{idx} [label=\"{label}\"];").unwrap(),
+                None => writeln!(dot, "{indent}{prefix}{parent} -> {prefix}{idx};").unwrap(),
+            }
+            *written += 1;
+        }
+
+        // the following nodes are the children of this node
+        if kind.arity() > 0 {
+            parents.push((idx, kind, 0));
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Enums for errors in ketchup
 
-use std::fmt::Debug;
+use core::fmt::{self, Debug, Display, Formatter};
 use crate::node;
 
 /// An error that can occur in ketchup
@@ -17,4 +17,192 @@ pub enum Error<'a, Node: node::Node> {
         /// The unary (right-aligned) or binary node found instead
         found: Node,
     },
+    /// Occurs when a mixfix node is missing the separator that closes one of it's holes (eg. the `:` in `a ? b : c`), includes the mixfix node
+    ExpectedSeparator(&'a Node),
+    /// Occurs when there is a separator but there isn't a mixfix, n-ary or postfix node with an open hole for it to close
+    UnexpectedSeparator,
+    /// Occurs when an n-ary (or postfix) node has a separator with nothing before it (eg. `f(a,,b)`), includes the n-ary node
+    EmptySlot(&'a Node),
+    /// Occurs when an n-ary (or postfix) node that doesn't allow trailing separators is closed after a separator (eg. `[a, b,]`), includes the n-ary node
+    TrailingSeparator(&'a Node),
+    /// Occurs when an n-ary (or postfix) node is missing it's closing delimiter (eg. the `]` in `[a, b]`), includes the n-ary node
+    ExpectedClose(&'a Node),
+    /// Occurs when there is a closing delimiter but there isn't an open n-ary (or postfix) node for it to close
+    UnexpectedClose,
+    /// Occurs when a fixed-capacity ASA has no room left for a node (or for the scope that it opens), includes the node
+    CapacityExceeded(Node),
+    /// Occurs when a group is opened when the ASA is already complete (eg. the `(` in `a (b)`)
+    UnexpectedGroup,
+    /// Occurs when a group is missing it's closing delimiter (eg. the `)` in `(a + b)`)
+    ExpectedCloseGroup,
+    /// Occurs when there is a closing delimiter but there isn't an open group for it to close
+    UnexpectedCloseGroup,
+    /// Occurs when a fixed-capacity ASA has no room left for the scope of a group
+    GroupCapacityExceeded,
 }
+
+impl<Node: node::Node> Error<'_, Node> {
+    /// Clones the nodes that the error borrows from the ASA, so that it can outlive the ASA (without their indexes, see [`parse::indexed`](crate::parse::indexed) for those)
+    #[inline]
+    pub fn into_owned(self) -> OwnedError<Node> {
+        let unindexed = |node: &Node| Indexed { idx: (), node: node.clone() };
+
+        match self {
+            Error::UnexpectedNode(node) => OwnedError::UnexpectedNode(node),
+            Error::ExpectedNode(None) => OwnedError::EmptyExpression,
+            Error::ExpectedNode(Some(oper)) => OwnedError::ExpectedNode(unindexed(oper)),
+            Error::UnexpectedExpectedNode { oper, found } => OwnedError::UnexpectedExpectedNode { oper: oper.map(unindexed), found },
+            Error::ExpectedSeparator(node) => OwnedError::ExpectedSeparator(unindexed(node)),
+            Error::UnexpectedSeparator => OwnedError::UnexpectedSeparator,
+            Error::EmptySlot(node) => OwnedError::EmptySlot(unindexed(node)),
+            Error::TrailingSeparator(node) => OwnedError::TrailingSeparator(unindexed(node)),
+            Error::ExpectedClose(node) => OwnedError::ExpectedClose(unindexed(node)),
+            Error::UnexpectedClose => OwnedError::UnexpectedClose,
+            Error::CapacityExceeded(node) => OwnedError::CapacityExceeded(node),
+            Error::UnexpectedGroup => OwnedError::UnexpectedGroup,
+            Error::ExpectedCloseGroup => OwnedError::ExpectedCloseGroup,
+            Error::UnexpectedCloseGroup => OwnedError::UnexpectedCloseGroup,
+            Error::GroupCapacityExceeded => OwnedError::GroupCapacityExceeded,
+        }
+    }
+}
+
+impl<Node: node::Node> From<Error<'_, Node>> for OwnedError<Node> {
+    #[inline]
+    fn from(error: Error<'_, Node>) -> Self {
+        error.into_owned()
+    }
+}
+
+/// A node that was borrowed from the ASA, alongside it's index in the ASA (or `()` when the error wasn't indexed)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Indexed<Node, Idx = usize> {
+    /// The index of the node in the ASA
+    pub idx: Idx,
+    /// The (cloned) node
+    pub node: Node,
+}
+
+impl<Node, Idx> Indexed<Node, Idx> {
+    /// Maps the node, keeping the index
+    #[inline]
+    pub fn map<M>(self, f: impl FnOnce(Node) -> M) -> Indexed<M, Idx> {
+        Indexed { idx: self.idx, node: f(self.node) }
+    }
+}
+
+impl<Node: Debug> Display for Indexed<Node> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} (at index {})", self.node, self.idx)
+    }
+}
+
+impl<Node: Debug> Display for Indexed<Node, ()> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.node)
+    }
+}
+
+/// An owned form of `Error`, with the nodes that it borrows from the ASA cloned, so that it can outlive the ASA (and be sent or stored elsewhere)
+///
+/// The borrowed nodes are recorded alongside their index in the ASA when the error comes from [`parse::indexed`](crate::parse::indexed) (see [`IndexedError`]), and without one when it comes from [`Error::into_owned`];
+/// unlike `Error`, an empty expression (`Error::ExpectedNode(None)`) is it's own variant, and the node type can be mapped (eg. to a span) with [`OwnedError::map_node`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OwnedError<Node, Idx = ()> {
+    /// See [`Error::UnexpectedNode`]
+    UnexpectedNode(Node),
+    /// Occurs when the ASA is incomplete without there being an operation node that requires a node, which only happens when it's expression (or slot) is empty
+    EmptyExpression,
+    /// See [`Error::ExpectedNode`], includes the operation node that requires the node
+    ExpectedNode(Indexed<Node, Idx>),
+    /// See [`Error::UnexpectedExpectedNode`]
+    UnexpectedExpectedNode {
+        /// The unary or binary node that requires the node (if there is one)
+        oper: Option<Indexed<Node, Idx>>,
+        /// The unary (right-aligned) or binary node found instead
+        found: Node,
+    },
+    /// See [`Error::ExpectedSeparator`]
+    ExpectedSeparator(Indexed<Node, Idx>),
+    /// See [`Error::UnexpectedSeparator`]
+    UnexpectedSeparator,
+    /// See [`Error::EmptySlot`]
+    EmptySlot(Indexed<Node, Idx>),
+    /// See [`Error::TrailingSeparator`]
+    TrailingSeparator(Indexed<Node, Idx>),
+    /// See [`Error::ExpectedClose`]
+    ExpectedClose(Indexed<Node, Idx>),
+    /// See [`Error::UnexpectedClose`]
+    UnexpectedClose,
+    /// See [`Error::CapacityExceeded`]
+    CapacityExceeded(Node),
+    /// See [`Error::UnexpectedGroup`]
+    UnexpectedGroup,
+    /// See [`Error::ExpectedCloseGroup`]
+    ExpectedCloseGroup,
+    /// See [`Error::UnexpectedCloseGroup`]
+    UnexpectedCloseGroup,
+    /// See [`Error::GroupCapacityExceeded`]
+    GroupCapacityExceeded,
+}
+
+/// An owned error that records the index (in the ASA) of every node that it borrows, alongside a clone of the node; see [`parse::indexed`](crate::parse::indexed) for parsing with indexed errors
+pub type IndexedError<Node> = OwnedError<Node, usize>;
+
+impl<Node, Idx> OwnedError<Node, Idx> {
+    /// Maps every node in the error (eg. to a span or to a name), keeping their indexes
+    pub fn map_node<M>(self, mut f: impl FnMut(Node) -> M) -> OwnedError<M, Idx> {
+        match self {
+            OwnedError::UnexpectedNode(node) => OwnedError::UnexpectedNode(f(node)),
+            OwnedError::EmptyExpression => OwnedError::EmptyExpression,
+            OwnedError::ExpectedNode(oper) => OwnedError::ExpectedNode(oper.map(f)),
+            OwnedError::UnexpectedExpectedNode { oper, found } => OwnedError::UnexpectedExpectedNode { oper: oper.map(|oper| oper.map(&mut f)), found: f(found) },
+            OwnedError::ExpectedSeparator(node) => OwnedError::ExpectedSeparator(node.map(f)),
+            OwnedError::UnexpectedSeparator => OwnedError::UnexpectedSeparator,
+            OwnedError::EmptySlot(node) => OwnedError::EmptySlot(node.map(f)),
+            OwnedError::TrailingSeparator(node) => OwnedError::TrailingSeparator(node.map(f)),
+            OwnedError::ExpectedClose(node) => OwnedError::ExpectedClose(node.map(f)),
+            OwnedError::UnexpectedClose => OwnedError::UnexpectedClose,
+            OwnedError::CapacityExceeded(node) => OwnedError::CapacityExceeded(f(node)),
+            OwnedError::UnexpectedGroup => OwnedError::UnexpectedGroup,
+            OwnedError::ExpectedCloseGroup => OwnedError::ExpectedCloseGroup,
+            OwnedError::UnexpectedCloseGroup => OwnedError::UnexpectedCloseGroup,
+            OwnedError::GroupCapacityExceeded => OwnedError::GroupCapacityExceeded,
+        }
+    }
+}
+
+impl<Node: Debug, Idx> Display for OwnedError<Node, Idx>
+where
+    Indexed<Node, Idx>: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            OwnedError::UnexpectedNode(node) => write!(f, "unexpected node {node:?} after a complete expression"),
+            OwnedError::EmptyExpression => write!(f, "expected an expression, found nothing"),
+            OwnedError::ExpectedNode(oper) => write!(f, "expected a node for {oper}"),
+            OwnedError::UnexpectedExpectedNode { oper: Some(oper), found } => write!(f, "expected a node for {oper}, found {found:?}"),
+            OwnedError::UnexpectedExpectedNode { oper: None, found } => write!(f, "expected a node, found {found:?}"),
+            OwnedError::ExpectedSeparator(node) => write!(f, "expected a separator for {node}"),
+            OwnedError::UnexpectedSeparator => write!(f, "unexpected separator outside of a mixfix, n-ary or postfix node"),
+            OwnedError::EmptySlot(node) => write!(f, "empty slot in {node}"),
+            OwnedError::TrailingSeparator(node) => write!(f, "trailing separator in {node}"),
+            OwnedError::ExpectedClose(node) => write!(f, "expected a closing delimiter for {node}"),
+            OwnedError::UnexpectedClose => write!(f, "unexpected closing delimiter outside of an n-ary or postfix node"),
+            OwnedError::CapacityExceeded(node) => write!(f, "no room left in the ASA for {node:?}"),
+            OwnedError::UnexpectedGroup => write!(f, "unexpected group after a complete expression"),
+            OwnedError::ExpectedCloseGroup => write!(f, "expected a closing delimiter for a group"),
+            OwnedError::UnexpectedCloseGroup => write!(f, "unexpected closing delimiter outside of a group"),
+            OwnedError::GroupCapacityExceeded => write!(f, "no room left in the ASA for a group"),
+        }
+    }
+}
+
+impl<Node: Debug, Idx: Debug> core::error::Error for OwnedError<Node, Idx>
+where
+    Indexed<Node, Idx>: Display,
+{}
@@ -1,12 +1,33 @@
 //! A parser that can *ketch - up* with your programming language.
 
 #![warn(missing_docs)]
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 pub mod node;
 pub mod asa;
 pub mod parse;
 pub mod error;
+pub mod view;
+pub mod compact;
+#[cfg(feature = "alloc")]
+pub mod visit;
+#[cfg(feature = "alloc")]
+pub mod tree;
+#[cfg(feature = "alloc")]
+pub mod render;
+#[cfg(feature = "alloc")]
+pub mod dot;
+#[cfg(feature = "alloc")]
+pub mod checkpoint;
+#[cfg(feature = "alloc")]
+pub mod table;
 pub mod prelude;
 
 /// The precedence of an operation / node
 pub type Precedence = usize;
+
+/// A precedence as it's declared (eg. copied from a language spec), which may be negative or have gaps, before it's remapped to a dense [`Precedence`]
+pub type DeclaredPrecedence = i64;
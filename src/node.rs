@@ -1,30 +1,103 @@
 //! Traits for nodes in the ASA
 
-use std::fmt::Debug;
+use core::fmt::Debug;
 use crate::Precedence;
 
+/// Derives [`Node`] for an enum through `#[operand]`, `#[unary(prec = ..)]`, `#[binary(prec = ..)]`, `#[mixfix(prec = ..)]`, `#[nary]` and `#[postfix(prec = ..)]` attributes on its variants
+pub use ketchup_derive::Node;
+
 /// An element in the **Abstract Syntax Array**
 ///
-/// Each node must have a 'type' the determines it's association, it's precedence and what kind of node it is; an operand, unary (left-aligned), unary (right-aligned) or a binary node
+/// Each node must have a 'type' the determines it's association, it's precedence and what kind of node it is; an operand, unary (left-aligned), unary (right-aligned), binary, mixfix, n-ary or a postfix node
 ///
-/// Nodes only need to be queried on their precedence and their kind (which is determined by their 'type')
+/// Nodes only need to be queried on their precedence, their kind and their associativity (which are determined by their 'type')
 pub trait Node: Debug + Clone {
     /// The maximum precedence value used for these nodes, precedence values MUST be in order and have **NO GAPS**
+    ///
+    /// Sparse precedences (eg. ones copied from a language spec) are remapped to dense ones by `#[derive(Node)]`, [`compact::dense`](crate::compact::dense) and the `table` module, so only hand-written implementations need to keep them dense
+    ///
+    /// Nodes with precedences that are only known at runtime (see the `table` module) use `Precedence::MAX`, and their ASAs are sized at runtime instead
     const MAX_PRECEDENCE: Precedence;
 
     /// Queries the precedence of the node (must be in order with **no gaps**)
     fn get_precedence(&self) -> Precedence;
     /// Queries the kind of node
     fn get_kind(&self) -> NodeKind;
+    /// Queries the associativity of the node (defaults to left-associative)
+    #[inline]
+    fn get_associativity(&self) -> Associativity {
+        Associativity::Left
+    }
+    /// Sets the arity of an n-ary (or postfix) node once it's closed (only ever called on n-ary and postfix nodes)
+    #[inline]
+    fn set_arity(&mut self, _arity: usize) {
+        unreachable!("only n-ary and postfix nodes have an arity");
+    }
 }
 
 /// Different kinds of nodes in the ASA
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NodeKind {
     /// A terminal node that doesn't require any 'parameters'
     Operand,
-    /// A node that has one 'parameters'
-    Unary,
+    /// A node that has one 'parameters', aligned to either side of it
+    Unary(Alignment),
     /// A node that has two 'parameters'
     Binary,
+    /// A node that has multiple 'parameters' separated by separators (eg. `a ? b : c` or `if a then b else c`)
+    Mixfix(Mixfix),
+    /// A node that has any amount of 'parameters' separated by separators and closed off by a closing delimiter (eg. `[a, b, c]`)
+    Nary(Nary),
+    /// A node that trails it's first 'parameter' (like a right-aligned unary node) with a list of arguments separated by separators and closed off by a closing delimiter (eg. `f(a, b)` or `xs[i]`), the arity is the amount of arguments
+    Postfix(Nary),
+}
+
+/// The layout of the 'parameters' of a mixfix node
+///
+/// The last 'parameter' of a mixfix node always trails it (like the right side of a binary node) and follows it's precedence and associativity, while all of the 'parameters' in it's *holes* are delimited by separators
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mixfix {
+    /// If there is a 'parameter' before the node (eg. `a` in `a ? b : c`, but not in `if a then b else c`)
+    pub leading: bool,
+    /// The amount of 'parameters' between the node and it's last separator, must be at least one (eg. `b` in `a ? b : c` or `a` and `b` in `if a then b else c`)
+    pub holes: usize,
+}
+
+/// The 'parameters' of an n-ary node (or the arguments of a postfix node)
+///
+/// N-ary nodes act like operands to the rest of the ASA, and are followed by all of their 'parameters' in order, while postfix nodes are followed by their operand and then all of their arguments in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Nary {
+    /// The amount of 'parameters' the node has (only known once the node is closed)
+    pub arity: usize,
+    /// If a separator is allowed after the last 'parameter' (eg. `[a, b,]`)
+    pub allow_trailing: bool,
+}
+
+impl Mixfix {
+    /// The mixfix layout of a ternary node (`a ? b : c`)
+    pub const TERNARY: Self = Self { leading: true, holes: 1 };
+}
+
+/// The side of a unary node that its 'parameter' is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alignment {
+    /// Comes before its 'parameter' (eg. `-1`)
+    Left,
+    /// Comes after its 'parameter' (eg. `foo()`)
+    Right,
+}
+
+/// How nodes of equal precedence group together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Associativity {
+    /// Equal precedence nodes group from the left (eg. `(1 - 2) - 3`)
+    Left,
+    /// Equal precedence nodes group from the right (eg. `2 ^ (3 ^ 4)`)
+    Right,
 }
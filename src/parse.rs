@@ -1,6 +1,8 @@
 //! Functions for parsing and manipulating the ASA
 
-use crate::{asa, error::Error, node::Node};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use crate::{asa::{self, Scope}, error::{Error, Indexed, IndexedError, OwnedError}, node::{Alignment, Associativity, Mixfix, Nary, Node, NodeKind}, Precedence};
 
 /// Returns a reference to the incomplete operation in the ASA
 pub fn incomplete_error<ASA: asa::ASA>(asa: &mut ASA) -> Option<&ASA::Node> {
@@ -16,24 +18,72 @@ pub fn incomplete_error<ASA: asa::ASA>(asa: &mut ASA) -> Option<&ASA::Node> {
     }
 }
 
+/// Returns if the current slot of an open n-ary (or postfix) node is empty (no nodes have been parsed since it's last separator or since it was opened)
+fn empty_slot<ASA: asa::ASA>(asa: &mut ASA) -> bool {
+    let Some(&mut Scope { node, group: false, .. }) = asa.scope() else {
+        return false;
+    };
+
+    matches!(asa.get_kind(node), NodeKind::Nary(_) | NodeKind::Postfix(_))
+        && !*asa.is_complete()
+        && *asa.last_incomplete() == Some(node)
+}
+
 /// Ensures that an ASA is completed, otherwise, returns a walked incomplete error
-pub fn ensure_completed<ASA: asa::ASA>(asa: &mut ASA) -> Result<(), Error<ASA::Node>> {
-    // check if the asa is complete
-    if *asa.is_complete() {
-        Ok(())
-    } else {
-        Err(Error::ExpectedNode(incomplete_error(asa)))
+pub fn ensure_completed<ASA: asa::ASA>(asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
+    // check if the asa is complete (an empty n-ary slot is reported as an unclosed n-ary node instead)
+    if !*asa.is_complete() && !empty_slot(asa) {
+        return Err(Error::ExpectedNode(incomplete_error(asa)));
+    }
+
+    // check if there are any mixfix nodes, n-ary nodes or groups that haven't been closed
+    if let Some(&mut scope) = asa.scope() {
+        return Err(unclosed(scope, asa));
     }
+
+    Ok(())
+}
+
+/// Returns the error for a scope that hasn't been closed
+fn unclosed<ASA: asa::ASA>(scope: Scope, asa: &mut ASA) -> Error<'_, ASA::Node> {
+    if scope.group {
+        return Error::ExpectedCloseGroup;
+    }
+
+    match asa.get_kind(scope.node) {
+        NodeKind::Nary(_) | NodeKind::Postfix(_) => Error::ExpectedClose(asa.get_node(scope.node)),
+        _ => Error::ExpectedSeparator(asa.get_node(scope.node)),
+    }
+}
+
+/// Returns the mixfix or n-ary node who's hole (or slot) is currently being filled, alongside which hole it is (starting from zero)
+///
+/// Returns `None` when a group is being filled instead, as groups don't belong to a node
+pub fn current_hole<ASA: asa::ASA>(asa: &mut ASA) -> Option<(&ASA::Node, usize)> {
+    let Scope { node, hole, group: false } = *asa.scope()? else {
+        return None;
+    };
+
+    Some((asa.get_node(node), hole))
+}
+
+/// Returns if the ASA has no room left for another node (or for the scope that the node opens)
+fn out_of_room<ASA: asa::ASA>(opens_scope: bool, asa: &ASA) -> bool {
+    asa.is_full() || (opens_scope && asa.scopes_full())
 }
 
 /// Parses an operand node and inserts it into the ASA
-pub fn operand<ASA: asa::ASA>(node: ASA::Node, asa: &mut ASA) -> Result<(), Error<ASA::Node>> {
+pub fn operand<ASA: asa::ASA>(node: ASA::Node, asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
     // check if the asa is complete, if so, throw error
     if *asa.is_complete() {
         return Err(Error::UnexpectedNode(node));
     }
+    if out_of_room(false, asa) {
+        return Err(Error::CapacityExceeded(node));
+    }
 
-    // otherwise, push it to the end of the ASA and update complete-ness field
+    // otherwise, push it to the end of the ASA and update complete-ness and last operand fields
+    *asa.last_operand() = asa.get_len();
     asa.push(node);
     *asa.is_complete() = true;
 
@@ -41,18 +91,23 @@ pub fn operand<ASA: asa::ASA>(node: ASA::Node, asa: &mut ASA) -> Result<(), Erro
 }
 
 /// Parses a left-aligned unary node and inserts it into the ASA
-pub fn unary_left_align<ASA: asa::ASA>(node: ASA::Node, asa: &mut ASA) -> Result<(), Error<ASA::Node>> {
+pub fn unary_left_align<ASA: asa::ASA>(node: ASA::Node, asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
     // check if the asa is complete, if so, throw error
     if *asa.is_complete() {
         return Err(Error::UnexpectedNode(node));
     }
+    if out_of_room(false, asa) {
+        return Err(Error::CapacityExceeded(node));
+    }
 
-    // otherwise update the lookup-table and
-    asa.lookuptable()[node.get_precedence()] = Some(asa.get_len());
-
-    // push it without modifying complete-ness
+    // otherwise push it without modifying complete-ness
+    let len = asa.get_len();
     asa.push(node);
 
+    // and update the lookup-table (if there isn't already an earlier node of the same precedence)
+    let precedence = asa.get_precedence(len);
+    asa.lookuptable()[precedence].get_or_insert(len);
+
     // also update the `last_incomplete` field
     *asa.last_incomplete() = Some(asa.get_len()-1);
 
@@ -61,44 +116,45 @@ pub fn unary_left_align<ASA: asa::ASA>(node: ASA::Node, asa: &mut ASA) -> Result
 
 /// Inserts a node into the ASA based on a precedence index lookup-table and it's association (left or right), returns the index of which the node was inserted at
 fn insert_lookuptable<ASA: asa::ASA>(node: ASA::Node, left_associative: bool, asa: &mut ASA) -> usize {
+    // the node isn't in the ASA yet, so this is the only time it's precedence is queried from the node itself
+    let precedence = node.get_precedence();
+
     // determine the range of precedences lower than the current one
     let range = if left_associative {
-        node.get_precedence().. // treat equal precedence as lesser than
+        precedence.. // treat equal precedence as lesser than
     } else {
-        node.get_precedence()+1.. // treat equal precedence as greater than
+        precedence+1.. // treat equal precedence as greater than
     };
 
     // iterate through the lookup-table and find any indexes of greater precedence
     let lower_prec = asa.lookuptable()[range]
         .iter()
-        .find_map(|idx| idx.clone());
+        .find_map(|idx| *idx);
 
-    // if there is one, then simply insert at that index and update the lookup-table and return
-    if let Some(idx) = lower_prec {
-        // update the lookup-table
-        asa.lookuptable()[node.get_precedence()] = Some(idx);
+    // if there is one, then simply insert at that index, otherwise, insert before the last operand of the ASA (not push)
+    let idx = lower_prec.unwrap_or(*asa.last_operand());
 
-        // update the indexes of greater precedence through incrementing them
-        for idx in asa.lookuptable()[node.get_precedence()+1..].iter_mut() {
-            if let Some(idx) = idx {
-                *idx += 1;
-            }
+    // any nodes at or after that index are now a part of the inserted node's 'parameter', so remove them from the lookup-table
+    for entry in asa.lookuptable().iter_mut() {
+        if entry.is_some_and(|entry| entry >= idx) {
+            *entry = None;
         }
-
-        asa.insert(idx, node);
-        return idx;
     }
 
-    // otherwise, simply insert at the end of the ASA and update the lookup-table
-    let end = asa.get_len() - 1;
-    asa.lookuptable()[node.get_precedence()] = Some(end);
-    asa.insert(end, node);
+    // update the lookup-table (if there isn't already an earlier node of the same precedence)
+    asa.lookuptable()[precedence].get_or_insert(idx);
+
+    // keep the last operand field pointing at the same node
+    if idx <= *asa.last_operand() {
+        *asa.last_operand() += 1;
+    }
 
-    end
+    asa.insert(idx, node);
+    idx
 }
 
 /// Parses a right-aligned unary node and inserts it into the ASA based on if it's right or left associative
-pub fn unary_right_align<ASA: asa::ASA>(node: ASA::Node, left_associative: bool, asa: &mut ASA) -> Result<(), Error<ASA::Node>> {
+pub fn unary_right_align<ASA: asa::ASA>(node: ASA::Node, left_associative: bool, asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
     // check if the asa is incomplete, if so, throw error
     if !*asa.is_complete() {
         return Err(Error::UnexpectedExpectedNode {
@@ -106,6 +162,9 @@ pub fn unary_right_align<ASA: asa::ASA>(node: ASA::Node, left_associative: bool,
             found: node,
         });
     }
+    if out_of_room(false, asa) {
+        return Err(Error::CapacityExceeded(node));
+    }
 
     // insert into the ASA based upon the lookup-table
     insert_lookuptable(node, left_associative, asa);
@@ -114,7 +173,7 @@ pub fn unary_right_align<ASA: asa::ASA>(node: ASA::Node, left_associative: bool,
 }
 
 /// Parses a binary node and inserts it into the ASA based on if it's left or right associative
-pub fn binary_node<ASA: asa::ASA>(node: ASA::Node, left_associative: bool, asa: &mut ASA) -> Result<(), Error<ASA::Node>> {
+pub fn binary_node<ASA: asa::ASA>(node: ASA::Node, left_associative: bool, asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
     // check if the asa is incomplete, if so, throw error
     if !*asa.is_complete() {
         return Err(Error::UnexpectedExpectedNode {
@@ -122,6 +181,9 @@ pub fn binary_node<ASA: asa::ASA>(node: ASA::Node, left_associative: bool, asa:
             found: node,
         });
     }
+    if out_of_room(false, asa) {
+        return Err(Error::CapacityExceeded(node));
+    }
 
     // insert into the ASA based upon the lookuptable
     let idx = insert_lookuptable(node, left_associative, asa);
@@ -132,3 +194,540 @@ pub fn binary_node<ASA: asa::ASA>(node: ASA::Node, left_associative: bool, asa:
 
     Ok(())
 }
+
+/// Opens the first hole of a mixfix or n-ary node (at the index provided) through saving and then clearing the lookup-table
+fn open_hole<ASA: asa::ASA>(idx: usize, asa: &mut ASA) {
+    asa.open_scope(idx);
+
+    // update the completeness fields
+    *asa.is_complete() = false;
+    *asa.last_incomplete() = Some(idx);
+}
+
+/// Parses a mixfix node, inserts it into the ASA based on if it's left or right associative and opens it's first hole
+///
+/// Panics if the node isn't a mixfix node
+pub fn mixfix<ASA: asa::ASA>(node: ASA::Node, left_associative: bool, asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
+    let NodeKind::Mixfix(Mixfix { leading, .. }) = node.get_kind() else {
+        panic!("`parse::mixfix` can only parse mixfix nodes");
+    };
+    let full = out_of_room(true, asa);
+
+    let idx = if leading {
+        // check if the asa is incomplete, if so, throw error
+        if !*asa.is_complete() {
+            return Err(Error::UnexpectedExpectedNode {
+                oper: incomplete_error(asa),
+                found: node,
+            });
+        }
+        if full {
+            return Err(Error::CapacityExceeded(node));
+        }
+
+        // insert into the ASA based upon the lookup-table (like a binary node)
+        insert_lookuptable(node, left_associative, asa)
+    } else {
+        // check if the asa is complete, if so, throw error
+        if *asa.is_complete() {
+            return Err(Error::UnexpectedNode(node));
+        }
+        if full {
+            return Err(Error::CapacityExceeded(node));
+        }
+
+        // push it and update the lookup-table (like a left-aligned unary node)
+        let len = asa.get_len();
+        asa.push(node);
+        let precedence = asa.get_precedence(len);
+        asa.lookuptable()[precedence].get_or_insert(len);
+
+        len
+    };
+
+    open_hole(idx, asa);
+
+    Ok(())
+}
+
+/// Parses a separator of a mixfix, n-ary or postfix node (eg. the `:` in `a ? b : c` or the `,` in `[a, b]`), closing the current hole and opening the next one (if there is one)
+pub fn separator<ASA: asa::ASA>(asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
+    // check if there is a hole to close, if not, throw error
+    let Some(&mut Scope { node: idx, group: false, .. }) = asa.scope() else {
+        return Err(Error::UnexpectedSeparator);
+    };
+
+    // make sure the contents of the hole are complete
+    if empty_slot(asa) {
+        return Err(Error::EmptySlot(asa.get_node(idx)));
+    }
+    if !*asa.is_complete() {
+        return Err(Error::ExpectedNode(incomplete_error(asa)));
+    }
+
+    // move on to the next hole
+    let scope = asa.scope().unwrap();
+    scope.hole += 1;
+    let hole = scope.hole;
+    let holes = match asa.get_kind(idx) {
+        NodeKind::Mixfix(Mixfix { holes, .. }) => holes,
+        NodeKind::Nary(_) | NodeKind::Postfix(_) => usize::MAX,
+        _ => unreachable!("only mixfix, n-ary and postfix nodes can open holes"),
+    };
+
+    if hole < holes {
+        // start the next hole with an empty lookup-table
+        asa.lookuptable().fill(None);
+    } else {
+        // otherwise, restore the lookup-table, so the last 'parameter' follows the precedence of the mixfix node
+        asa.close_scope();
+
+        // the nodes of greater precedence before the mixfix node can't take the last 'parameter' from it (eg. the `*` in `a * if b then c else d + e`), so remove them from the lookup-table
+        let precedence = asa.get_precedence(idx);
+        asa.lookuptable()[precedence+1..].fill(None);
+    }
+
+    // update the completeness fields
+    *asa.is_complete() = false;
+    *asa.last_incomplete() = Some(idx);
+
+    Ok(())
+}
+
+/// Parses an n-ary node, pushes it to the ASA and opens it's first slot
+pub fn nary<ASA: asa::ASA>(node: ASA::Node, asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
+    // check if the asa is complete, if so, throw error
+    if *asa.is_complete() {
+        return Err(Error::UnexpectedNode(node));
+    }
+    if out_of_room(true, asa) {
+        return Err(Error::CapacityExceeded(node));
+    }
+
+    // push it (it's an operand to the rest of the ASA, so it doesn't go in the lookup-table)
+    let idx = asa.get_len();
+    asa.push(node);
+
+    open_hole(idx, asa);
+
+    Ok(())
+}
+
+/// Parses a postfix node (eg. the `(` in `f(a, b)`), inserts it into the ASA like a right-aligned unary node (based on if it's left or right associative) and opens the slot of it's first argument
+///
+/// Panics if the node isn't a postfix node
+pub fn postfix<ASA: asa::ASA>(node: ASA::Node, left_associative: bool, asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
+    assert!(matches!(node.get_kind(), NodeKind::Postfix(_)), "`parse::postfix` can only parse postfix nodes");
+
+    // check if the asa is incomplete, if so, throw error
+    if !*asa.is_complete() {
+        return Err(Error::UnexpectedExpectedNode {
+            oper: incomplete_error(asa),
+            found: node,
+        });
+    }
+    if out_of_room(true, asa) {
+        return Err(Error::CapacityExceeded(node));
+    }
+
+    // insert into the ASA based upon the lookup-table, and then open the slot of the first argument (the arguments are closed off, so nodes in them can never be inserted before them)
+    let idx = insert_lookuptable(node, left_associative, asa);
+    open_hole(idx, asa);
+
+    Ok(())
+}
+
+/// Parses the closing delimiter of an n-ary or postfix node (eg. the `]` in `[a, b]` or the `)` in `f(a, b)`), setting it's arity and completing it
+pub fn close<ASA: asa::ASA>(asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
+    // check if there is an n-ary node to close, if not, throw error
+    let Some(&mut Scope { node: idx, hole, group }) = asa.scope() else {
+        return Err(Error::UnexpectedClose);
+    };
+    if group {
+        return Err(Error::ExpectedCloseGroup);
+    }
+    let (NodeKind::Nary(Nary { allow_trailing, .. }) | NodeKind::Postfix(Nary { allow_trailing, .. })) = asa.get_kind(idx) else {
+        return Err(Error::ExpectedSeparator(asa.get_node(idx)));
+    };
+
+    // determine the arity, allowing for empty n-ary nodes and trailing separators
+    let arity = if empty_slot(asa) {
+        if hole > 0 && !allow_trailing {
+            return Err(Error::TrailingSeparator(asa.get_node(idx)));
+        }
+        hole
+    } else if *asa.is_complete() {
+        hole + 1
+    } else {
+        return Err(Error::ExpectedNode(incomplete_error(asa)));
+    };
+
+    // restore the lookup-table
+    asa.close_scope();
+
+    // set the arity of the node and update the complete-ness and last operand fields
+    asa.set_arity(idx, arity);
+    *asa.is_complete() = true;
+    *asa.last_operand() = idx;
+
+    Ok(())
+}
+
+/// Opens a group (eg. the `(` in `(a + b) * c`), which is parsed like a separate expression and then becomes a single operand to the rest of the ASA once it's closed
+///
+/// Unlike parsing the group into a separate ASA, the group lives inline in the ASA (as the subtree of the operand it becomes); only the lookup-table is saved onto the stack of scopes, and then cleared, so that nodes in the group can never be inserted before it
+pub fn open_group<ASA: asa::ASA>(asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
+    // check if the asa is complete, if so, throw error
+    if *asa.is_complete() {
+        return Err(Error::UnexpectedGroup);
+    }
+    if asa.scopes_full() {
+        return Err(Error::GroupCapacityExceeded);
+    }
+
+    // the group starts where the next operand would be pushed
+    let start = asa.get_len();
+    asa.open_scope(start);
+    asa.scope().unwrap().group = true;
+
+    // the group is an empty expression of it's own, so there's no incomplete node yet
+    *asa.last_incomplete() = None;
+
+    Ok(())
+}
+
+/// Closes a group (eg. the `)` in `(a + b) * c`), restoring the lookup-table so that the whole group is treated as the last operand of the ASA
+pub fn close_group<ASA: asa::ASA>(asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
+    // check if there is a group to close, if not, throw error
+    let Some(&mut scope) = asa.scope() else {
+        return Err(Error::UnexpectedCloseGroup);
+    };
+    if !scope.group {
+        return Err(unclosed(scope, asa));
+    }
+
+    // make sure the contents of the group are complete
+    if !*asa.is_complete() {
+        return Err(Error::ExpectedNode(incomplete_error(asa)));
+    }
+
+    // restore the lookup-table, and point the last operand at the start of the group, so nodes of greater precedence take the whole group as their operand
+    asa.close_scope();
+    *asa.last_operand() = scope.node;
+
+    Ok(())
+}
+
+/// Parses any node and inserts it into the ASA based upon it's kind, alignment and associativity
+pub fn node<ASA: asa::ASA>(node: ASA::Node, asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
+    let left_associative = node.get_associativity() == Associativity::Left;
+
+    match node.get_kind() {
+        NodeKind::Operand => operand(node, asa),
+        NodeKind::Unary(Alignment::Left) => unary_left_align(node, asa),
+        NodeKind::Unary(Alignment::Right) => unary_right_align(node, left_associative, asa),
+        NodeKind::Binary => binary_node(node, left_associative, asa),
+        NodeKind::Mixfix(_) => mixfix(node, left_associative, asa),
+        NodeKind::Nary(_) => nary(node, asa),
+        NodeKind::Postfix(_) => postfix(node, left_associative, asa),
+    }
+}
+
+/// Returns the node of the innermost scope (panics if there aren't any scopes)
+fn scope_node<ASA: asa::ASA>(asa: &mut ASA) -> &ASA::Node {
+    let idx = asa.scope().expect("scope errors can only occur with an open scope").node;
+    asa.get_node(idx)
+}
+
+/// Rebuilds an owned error (see [`Error::into_owned`]) through borrowing it's nodes from the (unchanged) ASA again
+///
+/// Every node that an error borrows is either the last incomplete node or the node of the innermost scope, so the error can be rebuilt once the ASA is no longer mutably borrowed, as long as the ASA hasn't changed in between
+fn attach<ASA: asa::ASA>(error: OwnedError<ASA::Node>, asa: &mut ASA) -> Error<'_, ASA::Node> {
+    match error {
+        OwnedError::UnexpectedNode(node) => Error::UnexpectedNode(node),
+        OwnedError::EmptyExpression | OwnedError::ExpectedNode(_) => Error::ExpectedNode(incomplete_error(asa)),
+        OwnedError::UnexpectedExpectedNode { found, .. } => Error::UnexpectedExpectedNode { oper: incomplete_error(asa), found },
+        OwnedError::ExpectedSeparator(_) => Error::ExpectedSeparator(scope_node(asa)),
+        OwnedError::UnexpectedSeparator => Error::UnexpectedSeparator,
+        OwnedError::EmptySlot(_) => Error::EmptySlot(scope_node(asa)),
+        OwnedError::TrailingSeparator(_) => Error::TrailingSeparator(scope_node(asa)),
+        OwnedError::ExpectedClose(_) => Error::ExpectedClose(scope_node(asa)),
+        OwnedError::UnexpectedClose => Error::UnexpectedClose,
+        OwnedError::CapacityExceeded(node) => Error::CapacityExceeded(node),
+        OwnedError::UnexpectedGroup => Error::UnexpectedGroup,
+        OwnedError::ExpectedCloseGroup => Error::ExpectedCloseGroup,
+        OwnedError::UnexpectedCloseGroup => Error::UnexpectedCloseGroup,
+        OwnedError::GroupCapacityExceeded => Error::GroupCapacityExceeded,
+    }
+}
+
+/// Records the indexes of the nodes of an owned error (see [`Error::into_owned`]) from the (unchanged) ASA, in the same way as [`attach`]
+fn index<ASA: asa::ASA>(error: OwnedError<ASA::Node>, asa: &mut ASA) -> IndexedError<ASA::Node> {
+    let incomplete = *asa.last_incomplete();
+    let scope = asa.scope().map(|scope| scope.node);
+    let incomplete = |oper: Indexed<ASA::Node, ()>| Indexed { idx: incomplete.expect("operation errors can only occur with an incomplete node"), node: oper.node };
+    let scope = |node: Indexed<ASA::Node, ()>| Indexed { idx: scope.expect("scope errors can only occur with an open scope"), node: node.node };
+
+    match error {
+        OwnedError::UnexpectedNode(node) => OwnedError::UnexpectedNode(node),
+        OwnedError::EmptyExpression => OwnedError::EmptyExpression,
+        OwnedError::ExpectedNode(oper) => OwnedError::ExpectedNode(incomplete(oper)),
+        OwnedError::UnexpectedExpectedNode { oper, found } => OwnedError::UnexpectedExpectedNode { oper: oper.map(incomplete), found },
+        OwnedError::ExpectedSeparator(node) => OwnedError::ExpectedSeparator(scope(node)),
+        OwnedError::UnexpectedSeparator => OwnedError::UnexpectedSeparator,
+        OwnedError::EmptySlot(node) => OwnedError::EmptySlot(scope(node)),
+        OwnedError::TrailingSeparator(node) => OwnedError::TrailingSeparator(scope(node)),
+        OwnedError::ExpectedClose(node) => OwnedError::ExpectedClose(scope(node)),
+        OwnedError::UnexpectedClose => OwnedError::UnexpectedClose,
+        OwnedError::CapacityExceeded(node) => OwnedError::CapacityExceeded(node),
+        OwnedError::UnexpectedGroup => OwnedError::UnexpectedGroup,
+        OwnedError::ExpectedCloseGroup => OwnedError::ExpectedCloseGroup,
+        OwnedError::UnexpectedCloseGroup => OwnedError::UnexpectedCloseGroup,
+        OwnedError::GroupCapacityExceeded => OwnedError::GroupCapacityExceeded,
+    }
+}
+
+/// Runs a parsing operation on the ASA (eg. `|asa| parse::node(node, asa)`), turning any error it returns into an owned [`IndexedError`] that no longer borrows the ASA
+///
+/// ```
+/// # use ketchup::{asa::{ASA, VectorASA}, error::{Indexed, IndexedError}, node::Node, parse};
+/// # #[derive(Debug, Clone, PartialEq, Eq, Node)]
+/// # enum Expr { #[operand] Number(i32), #[binary(prec = 0)] Add }
+/// let mut asa = VectorASA::<Expr>::new(Expr::MAX_PRECEDENCE);
+/// parse::node(Expr::Number(1), &mut asa).unwrap();
+/// parse::node(Expr::Add, &mut asa).unwrap();
+///
+/// let error = parse::indexed(&mut asa, parse::ensure_completed).unwrap_err();
+/// assert_eq!(error, IndexedError::ExpectedNode(Indexed { idx: 0, node: Expr::Add }));
+/// ```
+pub fn indexed<ASA: asa::ASA, T>(
+    asa: &mut ASA,
+    parse: impl for<'a> FnOnce(&'a mut ASA) -> Result<T, Error<'a, ASA::Node>>,
+) -> Result<T, IndexedError<ASA::Node>> {
+    match parse(asa) {
+        Ok(output) => Ok(output),
+        Err(error) => {
+            // own the error so that the ASA is no longer borrowed
+            let error = error.into_owned();
+            Err(index(error, asa))
+        },
+    }
+}
+
+/// Parses every node from an iterator into the ASA (see [`node`]) and then ensures that the ASA is completed, stopping at the first error
+pub fn nodes<ASA: asa::ASA>(nodes: impl IntoIterator<Item = ASA::Node>, asa: &mut ASA) -> Result<(), Error<'_, ASA::Node>> {
+    for node in nodes {
+        if let Err(error) = self::node(node, asa) {
+            // own the error so that the ASA isn't borrowed across iterations
+            let error = error.into_owned();
+            return Err(attach(error, asa));
+        }
+    }
+
+    ensure_completed(asa)
+}
+
+/// Parses every node from a fallible iterator (such as a lexer) into the ASA (see [`node`]) and then ensures that the ASA is completed, stopping at the first error
+pub fn try_nodes<ASA, E>(nodes: impl IntoIterator<Item = Result<ASA::Node, E>>, asa: &mut ASA) -> Result<(), E>
+where
+    ASA: asa::ASA,
+    E: for<'a> From<Error<'a, ASA::Node>>,
+{
+    for node in nodes {
+        self::node(node?, asa)?;
+    }
+
+    Ok(ensure_completed(asa)?)
+}
+
+/// Parses every node from a fallible iterator (such as a lexer) into a new ASA and ensures that it's completed, like [`FromIterator`] does for [`Result`]
+#[inline]
+pub fn collect<ASA, E>(nodes: impl IntoIterator<Item = Result<ASA::Node, E>>) -> Result<ASA, E>
+where
+    ASA: asa::ASA,
+    E: for<'a> From<Error<'a, ASA::Node>>,
+{
+    collect_with(ASA::Node::MAX_PRECEDENCE, nodes)
+}
+
+/// Parses every node from a fallible iterator into a new ASA (see [`collect`]) with a maximum precedence that's only known at runtime (eg. from an [`OperatorTable`](crate::table::OperatorTable))
+pub fn collect_with<ASA, E>(max_precedence: Precedence, nodes: impl IntoIterator<Item = Result<ASA::Node, E>>) -> Result<ASA, E>
+where
+    ASA: asa::ASA,
+    E: for<'a> From<Error<'a, ASA::Node>>,
+{
+    let mut asa = ASA::new(max_precedence);
+    try_nodes(nodes, &mut asa)?;
+
+    Ok(asa)
+}
+
+/// Parses a single node into an owned ASA (see [`node`]) and returns the ASA back, so that it can be used with [`Iterator::try_fold`]
+///
+/// Note: this doesn't ensure that the ASA is completed once the iterator ends, so [`ensure_completed`] must still be called afterwards
+pub fn step<ASA, E>(mut asa: ASA, node: ASA::Node) -> Result<ASA, E>
+where
+    ASA: asa::ASA,
+    E: for<'a> From<Error<'a, ASA::Node>>,
+{
+    self::node(node, &mut asa)?;
+
+    Ok(asa)
+}
+
+/// How to recover from errors when parsing in recovery mode (see [`Recovering`])
+///
+/// Any closure that returns a placeholder operand (eg. `|| Expr::Error`) can be used to recover
+pub trait Recover<N: Node> {
+    /// Returns a placeholder 'error' operand, to insert wherever an operand is missing
+    fn placeholder(&mut self) -> N;
+
+    /// Returns a binary node to join a surplus node (a node found after an already completed expression) onto the rest of the expression with, or `None` to drop the surplus node (the default)
+    #[inline]
+    fn join(&mut self, _surplus: &N) -> Option<N> {
+        None
+    }
+}
+
+impl<N: Node, F: FnMut() -> N> Recover<N> for F {
+    #[inline]
+    fn placeholder(&mut self) -> N {
+        self()
+    }
+}
+
+/// A recovery mode for parsing, where errors are recorded and recovered from instead of stopping the parse, so that every problem in an expression can be reported at once
+///
+/// Missing operands are filled in with a placeholder operand, surplus nodes are either dropped or joined on (see [`Recover`]), stray separators, closing delimiters and groups are dropped, and any unclosed mixfix nodes, n-ary nodes or groups are closed when parsing is finished;
+/// so the ASA is always completed once [`Recovering::finish`] returns (unless a fixed-capacity ASA runs out of room)
+#[cfg(feature = "alloc")]
+pub struct Recovering<'a, ASA: asa::ASA, R: Recover<ASA::Node>> {
+    asa: &'a mut ASA,
+    recover: R,
+    errors: Vec<IndexedError<ASA::Node>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, ASA: asa::ASA, R: Recover<ASA::Node>> Recovering<'a, ASA, R> {
+    /// Starts parsing into an ASA in recovery mode
+    #[inline]
+    pub fn new(asa: &'a mut ASA, recover: R) -> Self {
+        Self { asa, recover, errors: Vec::new() }
+    }
+
+    /// Returns the errors that have been recovered from so far
+    #[inline]
+    pub fn errors(&self) -> &[IndexedError<ASA::Node>] {
+        &self.errors
+    }
+
+    /// Parses any node (see [`node`])
+    pub fn node(&mut self, node: ASA::Node) {
+        let mut next = Some(node);
+        while let Some(node) = next.take() {
+            let Err(error) = indexed(self.asa, |asa| self::node(node, asa)) else { return };
+            if self.fix(&error) {
+                // every error from parsing a node includes the node, so it only needs to be cloned when it's retried
+                next = Some(match &error {
+                    IndexedError::UnexpectedNode(node) | IndexedError::UnexpectedExpectedNode { found: node, .. } | IndexedError::CapacityExceeded(node) => node.clone(),
+                    _ => unreachable!("errors from parsing a node always include the node"),
+                });
+            }
+            self.errors.push(error);
+        }
+    }
+
+    /// Parses every node from an iterator (see [`node`])
+    #[inline]
+    pub fn nodes(&mut self, nodes: impl IntoIterator<Item = ASA::Node>) {
+        nodes.into_iter().for_each(|node| self.node(node));
+    }
+
+    /// Parses a separator of a mixfix or n-ary node (see [`separator`])
+    #[inline]
+    pub fn separator(&mut self) {
+        self.recover_from(separator);
+    }
+
+    /// Parses the closing delimiter of an n-ary node (see [`close`])
+    #[inline]
+    pub fn close(&mut self) {
+        self.recover_from(close);
+    }
+
+    /// Opens a group (see [`open_group`])
+    #[inline]
+    pub fn open_group(&mut self) {
+        self.recover_from(open_group);
+    }
+
+    /// Closes a group (see [`close_group`])
+    #[inline]
+    pub fn close_group(&mut self) {
+        self.recover_from(close_group);
+    }
+
+    /// Finishes parsing; completes the ASA (see [`ensure_completed`]) and returns every error that was recovered from
+    #[inline]
+    pub fn finish(mut self) -> Vec<IndexedError<ASA::Node>> {
+        self.recover_from(ensure_completed);
+        self.errors
+    }
+
+    /// Runs a parsing operation, recording and recovering from every error it returns until it succeeds (or the error can't be recovered from), returning if it succeeded
+    fn recover_from(&mut self, mut parse: impl for<'b> FnMut(&'b mut ASA) -> Result<(), Error<'b, ASA::Node>>) -> bool {
+        while let Err(error) = indexed(self.asa, &mut parse) {
+            let retry = self.fix(&error);
+            self.errors.push(error);
+            if !retry {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Inserts a placeholder operand into the ASA, returning if it succeeded
+    #[inline]
+    fn placeholder(&mut self) -> bool {
+        operand(self.recover.placeholder(), self.asa).is_ok()
+    }
+
+    /// Fixes the ASA so that the operation that caused an error can be retried, returning if it should be retried
+    fn fix(&mut self, error: &IndexedError<ASA::Node>) -> bool {
+        match error {
+            // join on the surplus node (or drop it)
+            IndexedError::UnexpectedNode(surplus) => match self.recover.join(surplus) {
+                Some(join) => self::node(join, self.asa).is_ok(),
+                None => false,
+            },
+
+            // fill in the missing operand
+            IndexedError::EmptyExpression
+            | IndexedError::ExpectedNode(_)
+            | IndexedError::UnexpectedExpectedNode { .. }
+            | IndexedError::EmptySlot(_)
+            | IndexedError::TrailingSeparator(_) => self.placeholder(),
+
+            // close the unclosed hole, n-ary node or group, and only retry if it was actually closed (so a full ASA can't make it retry forever)
+            IndexedError::ExpectedSeparator(_) => (*self.asa.is_complete() || self.placeholder()) && self.recover_from(separator),
+            IndexedError::ExpectedClose(_) => self.recover_from(close),
+            IndexedError::ExpectedCloseGroup => self.recover_from(close_group),
+
+            // drop the stray separator, closing delimiter or group, and stop when there's no room left
+            IndexedError::UnexpectedSeparator
+            | IndexedError::UnexpectedClose
+            | IndexedError::UnexpectedGroup
+            | IndexedError::UnexpectedCloseGroup
+            | IndexedError::CapacityExceeded(_)
+            | IndexedError::GroupCapacityExceeded => false,
+        }
+    }
+}
+
+/// Parses every node from an iterator into the ASA in recovery mode (see [`Recovering`]), returning every error that was recovered from
+#[cfg(feature = "alloc")]
+pub fn nodes_recovering<ASA: asa::ASA>(nodes: impl IntoIterator<Item = ASA::Node>, asa: &mut ASA, recover: impl Recover<ASA::Node>) -> Vec<IndexedError<ASA::Node>> {
+    let mut recovering = Recovering::new(asa, recover);
+    recovering.nodes(nodes);
+    recovering.finish()
+}
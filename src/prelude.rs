@@ -1,7 +1,18 @@
 //! Common imports to get you up and running with ketchup
 
 pub use crate::Precedence;
-pub use crate::asa::{ASA, VectorASA};
+pub use crate::asa::{ASA, ArrayASA};
+#[cfg(feature = "alloc")]
+pub use crate::asa::{DequeASA, RopeASA, SoaASA, VectorASA};
 pub use crate::error::Error as KError;
-pub use crate::node::{Node, NodeKind};
+pub use crate::node::{Alignment, Associativity, Node, NodeKind};
 pub use crate::parse;
+pub use crate::view::{AsaView, View};
+#[cfg(feature = "alloc")]
+pub use crate::view::SkipView;
+#[cfg(feature = "alloc")]
+pub use crate::render;
+#[cfg(feature = "alloc")]
+pub use crate::tree::Tree;
+#[cfg(feature = "alloc")]
+pub use crate::visit::{self, TryVisitor, Visitor, VisitorMut};
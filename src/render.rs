@@ -0,0 +1,146 @@
+//! Human-readable renderings of completed ASAs, for debugging and testing
+//!
+//! Each rendering formats the nodes with `Display` when it's displayed (`{}`) and with `Debug` when it's debugged (`{:?}`)
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+use crate::{node::NodeKind, view::View};
+
+/// Renders a completed ASA (or view) as an indented tree drawn with box-drawing characters, with one node per line
+///
+/// ```text
+/// Add
+/// ├── Number(12)
+/// └── Mul
+///     ├── Number(4)
+///     └── Number(8)
+/// ```
+#[inline]
+pub fn tree<V: View + ?Sized>(view: &V) -> TreeRender<'_, V> {
+    TreeRender(view)
+}
+
+/// Renders a completed ASA (or view) as an S-expression, with operands left bare and every other node wrapped in parentheses alongside it's 'parameters'
+///
+/// ```text
+/// (Add Number(12) (Mul Number(4) Number(8)))
+/// ```
+#[inline]
+pub fn sexpr<V: View + ?Sized>(view: &V) -> SExprRender<'_, V> {
+    SExprRender(view)
+}
+
+/// An indented tree rendering of an ASA (see [`tree`])
+#[derive(Clone, Copy)]
+pub struct TreeRender<'a, V: View + ?Sized>(&'a V);
+
+/// An S-expression rendering of an ASA (see [`sexpr`])
+#[derive(Clone, Copy)]
+pub struct SExprRender<'a, V: View + ?Sized>(&'a V);
+
+impl<V: View + ?Sized> TreeRender<'_, V> {
+    fn render(&self, f: &mut Formatter<'_>, fmt_node: fn(&V::Node, &mut Formatter<'_>) -> fmt::Result) -> fmt::Result {
+        // the amount of children left to render for each of the ancestors of the current node
+        let mut remaining: Vec<usize> = Vec::new();
+
+        for idx in 0..self.0.len() {
+            // pop off any ancestors that have had all of their children rendered
+            while remaining.last() == Some(&0) {
+                remaining.pop();
+            }
+
+            if idx > 0 {
+                writeln!(f)?;
+            }
+
+            // draw the lines of the ancestors that still have children after this branch, and then the node's own branch
+            if let Some((parent, ancestors)) = remaining.split_last_mut() {
+                for ancestor in ancestors.iter() {
+                    f.write_str(if *ancestor > 0 { "│   " } else { "    " })?;
+                }
+                f.write_str(if *parent == 1 { "└── " } else { "├── " })?;
+                *parent -= 1;
+            }
+
+            let node = self.0.node(idx);
+            fmt_node(node, f)?;
+
+            // the following nodes are the children of this node
+            let arity = self.0.kind(idx).arity();
+            if arity > 0 {
+                remaining.push(arity);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<V: View + ?Sized> SExprRender<'_, V> {
+    fn render(&self, f: &mut Formatter<'_>, fmt_node: fn(&V::Node, &mut Formatter<'_>) -> fmt::Result) -> fmt::Result {
+        // the amount of children left to render for each of the open parentheses
+        let mut remaining: Vec<usize> = Vec::new();
+
+        for idx in 0..self.0.len() {
+            if idx > 0 {
+                f.write_str(" ")?;
+            }
+
+            let node = self.0.node(idx);
+            let kind = self.0.kind(idx);
+            if kind == NodeKind::Operand {
+                fmt_node(node, f)?;
+            } else {
+                f.write_str("(")?;
+                fmt_node(node, f)?;
+
+                // leave the parentheses open for the children of the node
+                if kind.arity() > 0 {
+                    remaining.push(kind.arity());
+                    continue;
+                }
+                f.write_str(")")?;
+            }
+
+            // close the parentheses of every node that was only waiting on this one
+            while let Some(parent) = remaining.last_mut() {
+                *parent -= 1;
+                if *parent > 0 {
+                    break;
+                }
+                remaining.pop();
+                f.write_str(")")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<V: View + ?Sized> Display for TreeRender<'_, V> where V::Node: Display {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.render(f, |node, f| write!(f, "{node}"))
+    }
+}
+
+impl<V: View + ?Sized> Debug for TreeRender<'_, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.render(f, |node, f| write!(f, "{node:?}"))
+    }
+}
+
+impl<V: View + ?Sized> Display for SExprRender<'_, V> where V::Node: Display {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.render(f, |node, f| write!(f, "{node}"))
+    }
+}
+
+impl<V: View + ?Sized> Debug for SExprRender<'_, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.render(f, |node, f| write!(f, "{node:?}"))
+    }
+}
@@ -0,0 +1,171 @@
+//! Tables of operators that are built at runtime (eg. for languages with user-defined operators like `infixl 6 <+>`), and nodes that resolve their kind, precedence and associativity through them
+//!
+//! As the precedences are only known at runtime, ASAs of [`Dynamic`] nodes must be created through the table (see [`OperatorTable::asa`]), so that their lookup-tables are sized from the table instead of from `Node::MAX_PRECEDENCE`
+//!
+//! Declared precedences may be sparse or negative (eg. `infixl 60 +`, `infixl 70 *` and `infixr -1 $`), as the table remaps them to dense indexes in the same order when resolving nodes
+
+use alloc::collections::BTreeMap;
+use core::{borrow::Borrow, fmt::{self, Debug, Display, Formatter}};
+use crate::{asa::ASA, compact::{has_precedence, Operator}, node::{Associativity, Nary, Node, NodeKind}, DeclaredPrecedence, Precedence};
+
+/// A table of operators declared at runtime, mapping the symbol of each operator to it's kind, precedence and associativity
+///
+/// ```
+/// use ketchup::{asa::{ASA, VectorASA}, compact::Operator, node::Associativity, parse, table::{Dynamic, OperatorTable}};
+///
+/// // infixl 6 <+>
+/// // infixr 5 <:>
+/// let mut table = OperatorTable::new();
+/// table.declare("<+>", Operator::binary(6));
+/// table.declare("<:>", Operator::binary(5).assoc(Associativity::Right));
+///
+/// // a <:> b <+> c
+/// let mut asa: VectorASA<_> = table.asa();
+/// parse::node(Dynamic::operand("a"), &mut asa).unwrap();
+/// parse::node(table.node("<:>", "<:>").unwrap(), &mut asa).unwrap();
+/// parse::node(Dynamic::operand("b"), &mut asa).unwrap();
+/// parse::node(table.node("<+>", "<+>").unwrap(), &mut asa).unwrap();
+/// parse::node(Dynamic::operand("c"), &mut asa).unwrap();
+/// parse::ensure_completed(&mut asa).unwrap();
+///
+/// assert_eq!(asa.vector.iter().map(|node| node.payload).collect::<Vec<_>>(), ["<:>", "a", "<+>", "b", "c"]);
+/// assert_eq!(asa.lookuptable().len(), 2); // only two precedences are used
+/// ```
+#[derive(Debug, Clone)]
+pub struct OperatorTable<S: Ord> {
+    operators: BTreeMap<S, Operator>,
+    /// The amount of operators declared with each precedence
+    levels: BTreeMap<DeclaredPrecedence, usize>,
+}
+
+impl<S: Ord> Default for OperatorTable<S> {
+    #[inline]
+    fn default() -> Self {
+        Self { operators: BTreeMap::new(), levels: BTreeMap::new() }
+    }
+}
+
+impl<S: Ord> OperatorTable<S> {
+    /// Creates an empty table
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares an operator (operands don't need to be declared), returning the operator it replaces if the symbol was already declared
+    pub fn declare(&mut self, symbol: S, operator: Operator) -> Option<Operator> {
+        if has_precedence(&operator) {
+            *self.levels.entry(operator.precedence).or_insert(0) += 1;
+        }
+
+        let old = self.operators.insert(symbol, operator)?;
+        if has_precedence(&old) {
+            let Some(count) = self.levels.get_mut(&old.precedence) else { unreachable!("every declared precedence has a level") };
+            *count -= 1;
+            if *count == 0 {
+                self.levels.remove(&old.precedence);
+            }
+        }
+        Some(old)
+    }
+
+    /// Queries the operator of a symbol, as it was declared (with it's sparse precedence)
+    #[inline]
+    pub fn get<Q: Ord + ?Sized>(&self, symbol: &Q) -> Option<&Operator>
+    where
+        S: Borrow<Q>,
+    {
+        self.operators.get(symbol)
+    }
+
+    /// Returns the largest dense precedence of the declared operators (operands and n-ary nodes don't have a precedence)
+    #[inline]
+    pub fn max_precedence(&self) -> Precedence {
+        self.levels.len().saturating_sub(1)
+    }
+
+    /// Remaps a declared (sparse) precedence to it's dense index; the amount of distinct precedences declared below it
+    #[inline]
+    pub fn dense(&self, precedence: DeclaredPrecedence) -> Precedence {
+        self.levels.range(..precedence).count()
+    }
+
+    /// Creates an empty ASA with a lookup-table sized for the declared operators
+    ///
+    /// Declaring operators after the ASA is created must not add or remove any precedences, as that would shift the dense precedences of the nodes already in it
+    #[inline]
+    pub fn asa<A: ASA>(&self) -> A {
+        A::new(self.max_precedence())
+    }
+
+    /// Resolves the operator of a symbol into a node with a payload (eg. the symbol itself, or a span), or `None` if the symbol wasn't declared
+    #[inline]
+    pub fn node<Q: Ord + ?Sized, P: Debug + Clone>(&self, symbol: &Q, payload: P) -> Option<Dynamic<P>>
+    where
+        S: Borrow<Q>,
+    {
+        self.get(symbol).map(|operator| Dynamic { operator: *operator, precedence: self.dense(operator.precedence), payload })
+    }
+}
+
+/// A node who's kind, precedence and associativity were resolved at runtime through an [`OperatorTable`], alongside a payload
+///
+/// The precedence isn't known at compile time, so `MAX_PRECEDENCE` is `Precedence::MAX`; ASAs of dynamic nodes must be created with [`OperatorTable::asa`] (or with `OperatorTable::max_precedence`) instead,
+/// and can't be stored in an `ArrayASA` (the ASAs that allocate their lookup-table panic when created with `Precedence::MAX`, so APIs that use `MAX_PRECEDENCE` like `parse::collect` and `Tree::into_asa` have `_with` variants to use instead)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dynamic<P: Debug + Clone> {
+    operator: Operator,
+    /// The dense precedence of the operator
+    precedence: Precedence,
+    /// The payload of the node
+    pub payload: P,
+}
+
+impl<P: Debug + Clone> Dynamic<P> {
+    /// Creates an operand node with a payload (eg. a literal), as operands don't need to be declared
+    #[inline]
+    pub fn operand(payload: P) -> Self {
+        Self { operator: Operator::operand(), precedence: 0, payload }
+    }
+
+    /// Returns the operator that the node was resolved to, as it was declared (the arity of n-ary and postfix nodes is kept up to date), while `Node::get_precedence` returns it's dense precedence
+    #[inline]
+    pub fn operator(&self) -> &Operator {
+        &self.operator
+    }
+}
+
+impl<P: Debug + Clone + Display> Display for Dynamic<P> {
+    /// Displays the payload of the node
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.payload, f)
+    }
+}
+
+impl<P: Debug + Clone> Node for Dynamic<P> {
+    const MAX_PRECEDENCE: Precedence = Precedence::MAX;
+
+    #[inline]
+    fn get_precedence(&self) -> Precedence {
+        self.precedence
+    }
+
+    #[inline]
+    fn get_kind(&self) -> NodeKind {
+        self.operator.kind
+    }
+
+    #[inline]
+    fn get_associativity(&self) -> Associativity {
+        self.operator.associativity
+    }
+
+    #[inline]
+    fn set_arity(&mut self, arity: usize) {
+        match &mut self.operator.kind {
+            NodeKind::Nary(Nary { arity: old, .. }) | NodeKind::Postfix(Nary { arity: old, .. }) => *old = arity,
+            _ => unreachable!("only n-ary and postfix nodes have an arity"),
+        }
+    }
+}
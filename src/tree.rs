@@ -0,0 +1,171 @@
+//! A boxed tree representation of an ASA, for passes that are easier to write over a pointer tree
+
+use alloc::{boxed::Box, vec, vec::Vec};
+use crate::{asa::{ASA, VectorASA}, node::{Alignment, Associativity, Mixfix, Node, NodeKind}, view::View, visit::{self, Visitor}, Precedence};
+
+/// A node and all of it's 'parameters' (children) as a pointer tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tree<N: Node> {
+    /// An operand node
+    Operand(N),
+    /// A unary node and it's child
+    Unary(N, Box<Tree<N>>),
+    /// A binary node and it's children
+    Binary(N, Box<Tree<N>>, Box<Tree<N>>),
+    /// A mixfix node and all of it's 'parameters' (in order)
+    Mixfix(N, Vec<Tree<N>>),
+    /// An n-ary node and all of it's 'parameters' (in order)
+    Nary(N, Vec<Tree<N>>),
+    /// A postfix node, it's operand and all of it's arguments (in order)
+    Postfix(N, Box<Tree<N>>, Vec<Tree<N>>),
+}
+
+/// Builds trees out of the nodes of an ASA
+struct Builder;
+
+impl<N: Node> Visitor<N> for Builder {
+    type Output = Tree<N>;
+
+    #[inline]
+    fn visit_operand(&mut self, node: &N) -> Tree<N> {
+        Tree::Operand(node.clone())
+    }
+
+    #[inline]
+    fn visit_unary(&mut self, node: &N, child: Tree<N>) -> Tree<N> {
+        Tree::Unary(node.clone(), Box::new(child))
+    }
+
+    #[inline]
+    fn visit_binary(&mut self, node: &N, lhs: Tree<N>, rhs: Tree<N>) -> Tree<N> {
+        Tree::Binary(node.clone(), Box::new(lhs), Box::new(rhs))
+    }
+
+    #[inline]
+    fn visit_mixfix(&mut self, node: &N, params: Vec<Tree<N>>) -> Tree<N> {
+        Tree::Mixfix(node.clone(), params)
+    }
+
+    #[inline]
+    fn visit_nary(&mut self, node: &N, params: Vec<Tree<N>>) -> Tree<N> {
+        Tree::Nary(node.clone(), params)
+    }
+
+    #[inline]
+    fn visit_postfix(&mut self, node: &N, operand: Tree<N>, args: Vec<Tree<N>>) -> Tree<N> {
+        Tree::Postfix(node.clone(), Box::new(operand), args)
+    }
+}
+
+impl<N: Node> Tree<N> {
+    /// Builds a tree out of a completed ASA (or view) (panics if the ASA is incomplete)
+    #[inline]
+    pub fn from_view<V: View<Node = N> + ?Sized>(view: &V) -> Self {
+        visit::walk(view, &mut Builder)
+    }
+
+    /// Returns the root node of the tree
+    #[inline]
+    pub fn node(&self) -> &N {
+        match self {
+            Tree::Operand(node)
+            | Tree::Unary(node, _)
+            | Tree::Binary(node, _, _)
+            | Tree::Mixfix(node, _)
+            | Tree::Nary(node, _)
+            | Tree::Postfix(node, _, _) => node,
+        }
+    }
+
+    /// Flattens the tree back into a completed ASA, in the same state that parsing it would have left the ASA in (so that more nodes can still be parsed onto it)
+    ///
+    /// Trees don't record groups, so a node that binds looser than the node above it (eg. the `+` in `2 * (3 + 4)`) is treated as a closed group, as that's the only way the parser could have put it there
+    #[inline]
+    pub fn into_asa<A: ASA<Node = N>>(self) -> A {
+        self.into_asa_with(N::MAX_PRECEDENCE)
+    }
+
+    /// Flattens the tree back into a completed ASA (see [`Tree::into_asa`]) with a maximum precedence that's only known at runtime (eg. from an [`OperatorTable`](crate::table::OperatorTable))
+    pub fn into_asa_with<A: ASA<Node = N>>(self, max_precedence: Precedence) -> A {
+        let mut asa = A::new(max_precedence);
+
+        // the trees that still have to be pushed (with the next one on top), alongside if they're on the right-most path of the whole tree
+        // (the last 'parameter' of every node above it, as long as it isn't closed off by a delimiter like the 'parameters' of n-ary nodes),
+        // and the precedence of the node above them (if it has one)
+        let mut stack = vec![(self, true, None)];
+        while let Some((tree, rightmost, parent)) = stack.pop() {
+            let kind = tree.node().get_kind();
+            let precedence = (!matches!(kind, NodeKind::Operand | NodeKind::Nary(_))).then(|| tree.node().get_precedence());
+
+            // a node that the parser inserts through the lookup-table, but that binds looser than the node above it, can only have got there inside of a group (eg. the `+` in `2 * (3 + 4)`),
+            // and a closed group is just an operand to the rest of the ASA
+            let inserted = matches!(kind, NodeKind::Binary | NodeKind::Unary(Alignment::Right) | NodeKind::Mixfix(Mixfix { leading: true, .. }) | NodeKind::Postfix(_));
+            let group = rightmost && inserted && parent.zip(precedence).is_some_and(|(parent, precedence)| {
+                precedence < parent || (precedence == parent && tree.node().get_associativity() == Associativity::Left)
+            });
+            let inside = rightmost && !group;
+
+            let node = match tree {
+                Tree::Operand(node) => node,
+                Tree::Unary(node, child) => {
+                    stack.push((*child, inside, precedence));
+                    node
+                },
+                Tree::Binary(node, lhs, rhs) => {
+                    stack.push((*rhs, inside, precedence));
+                    stack.push((*lhs, false, precedence));
+                    node
+                },
+                Tree::Mixfix(node, params) => {
+                    let last = params.len() - 1;
+                    stack.extend(params.into_iter().enumerate().rev().map(|(pos, param)| (param, inside && pos == last, precedence)));
+                    node
+                },
+                Tree::Nary(mut node, params) => {
+                    node.set_arity(params.len());
+                    stack.extend(params.into_iter().rev().map(|param| (param, false, precedence)));
+                    node
+                },
+                Tree::Postfix(mut node, operand, args) => {
+                    node.set_arity(args.len());
+                    stack.extend(args.into_iter().rev().map(|arg| (arg, false, precedence)));
+                    stack.push((*operand, false, precedence));
+                    node
+                },
+            };
+
+            // the right-most path is the only path the parser can still insert into,
+            // so the nodes along it go in the lookup-table, and the operand (or closed n-ary, postfix node or group) at the end of it is the last operand
+            let idx = asa.get_len();
+            if group {
+                *asa.last_operand() = idx;
+            } else if rightmost {
+                if let Some(precedence) = precedence {
+                    asa.lookuptable()[precedence].get_or_insert(idx);
+                }
+                if matches!(kind, NodeKind::Operand | NodeKind::Nary(_) | NodeKind::Postfix(_)) {
+                    *asa.last_operand() = idx;
+                }
+            }
+
+            asa.push(node);
+        }
+
+        *asa.is_complete() = true;
+        asa
+    }
+}
+
+impl<N: Node> From<Tree<N>> for VectorASA<N> {
+    #[inline]
+    fn from(tree: Tree<N>) -> Self {
+        tree.into_asa()
+    }
+}
+
+impl<N: Node> From<&VectorASA<N>> for Tree<N> {
+    #[inline]
+    fn from(asa: &VectorASA<N>) -> Self {
+        Tree::from_view(asa)
+    }
+}
@@ -0,0 +1,361 @@
+//! Read-only views for walking a completed ASA like a tree
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::ops::Range;
+use crate::{asa::ASA, node::{Node, NodeKind}};
+
+impl NodeKind {
+    /// Returns the amount of 'parameters' (children) of the kind of node
+    #[inline]
+    pub fn arity(&self) -> usize {
+        match self {
+            NodeKind::Operand => 0,
+            NodeKind::Unary(_) => 1,
+            NodeKind::Binary => 2,
+            NodeKind::Mixfix(mixfix) => mixfix.leading as usize + mixfix.holes + 1,
+            NodeKind::Nary(nary) => nary.arity,
+            NodeKind::Postfix(args) => 1 + args.arity,
+        }
+    }
+}
+
+/// A read-only view of a **completed** ASA (or one of it's subtrees), that can be walked like a tree
+///
+/// Indexes are the same as the indexes of the ASA, with the root node at index zero and each node followed by all of it's children in order
+pub trait View {
+    /// The internal node implementation
+    type Node: Node;
+
+    /// Queries a node in the view (panic on out-of-bounds index)
+    fn node(&self, idx: usize) -> &Self::Node;
+    /// Queries the length of the view
+    fn len(&self) -> usize;
+
+    /// Queries the kind of a node in the view (panic on out-of-bounds index)
+    #[inline]
+    fn kind(&self, idx: usize) -> NodeKind {
+        self.node(idx).get_kind()
+    }
+
+    /// Returns if the view has no nodes
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index just after the end of the subtree of a node
+    fn subtree_end(&self, idx: usize) -> usize {
+        // walk through the nodes until there are no more expected children
+        let mut end = idx;
+        let mut expected = 1;
+        while expected > 0 {
+            expected += self.kind(end).arity();
+            expected -= 1;
+            end += 1;
+        }
+
+        end
+    }
+
+    /// Returns the range of indexes that make up the subtree of a node (including the node itself)
+    #[inline]
+    fn subtree_range(&self, idx: usize) -> Range<usize> {
+        idx..self.subtree_end(idx)
+    }
+
+    /// Returns a view of the subtree of a node
+    #[inline]
+    fn subtree(&self, idx: usize) -> AsaView<'_, Self> {
+        AsaView {
+            view: self,
+            range: self.subtree_range(idx),
+        }
+    }
+
+    /// Returns an iterator over the indexes of the children of a node (in order)
+    #[inline]
+    fn children(&self, idx: usize) -> Children<'_, Self> {
+        Children {
+            view: self,
+            next: idx + 1,
+            remaining: self.kind(idx).arity(),
+        }
+    }
+
+    /// Returns the index of the parent of a node (`None` for the root node)
+    ///
+    /// Takes one backwards pass from the node to it's parent, so it's `O(n)` in the distance between them (the sizes of the earlier siblings' subtrees)
+    fn parent(&self, idx: usize) -> Option<usize> {
+        // walk backwards, counting the subtrees that come before the node (and the node's own subtree), until a node's children include the node's subtree
+        let mut subtrees = 1;
+        for parent in (0..idx).rev() {
+            let arity = self.kind(parent).arity();
+            if arity >= subtrees {
+                return Some(parent);
+            }
+            subtrees = subtrees + 1 - arity;
+        }
+
+        None
+    }
+
+    /// Returns the depth of a node (the root node has a depth of zero)
+    ///
+    /// Takes one backwards pass from the node to the root node, so it's `O(n)` in the index of the node
+    fn depth(&self, idx: usize) -> usize {
+        // like `parent`, but keep walking after finding the parent (from the subtree of the parent instead), counting each ancestor
+        let mut depth = 0;
+        let mut subtrees = 1;
+        for ancestor in (0..idx).rev() {
+            let arity = self.kind(ancestor).arity();
+            if arity >= subtrees {
+                depth += 1;
+                subtrees = 1;
+            } else {
+                subtrees = subtrees + 1 - arity;
+            }
+        }
+
+        depth
+    }
+
+    /// Returns an iterator over the index, depth and node of every node in the view (in order)
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn iter_with_depth(&self) -> WithDepth<'_, Self> {
+        WithDepth {
+            view: self,
+            idx: 0,
+            remaining: Vec::new(),
+        }
+    }
+}
+
+impl<T: ASA> View for T {
+    type Node = T::Node;
+
+    #[inline]
+    fn node(&self, idx: usize) -> &Self::Node {
+        self.get_node(idx)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.get_len()
+    }
+
+    #[inline]
+    fn kind(&self, idx: usize) -> NodeKind {
+        self.get_kind(idx)
+    }
+}
+
+/// A borrowed view of a single subtree of an ASA (or of another view), that can be used like a whole ASA
+///
+/// The root node of the subtree is at index zero
+#[derive(Debug)]
+pub struct AsaView<'a, V: View + ?Sized> {
+    view: &'a V,
+    range: Range<usize>,
+}
+
+impl<V: View + ?Sized> Clone for AsaView<'_, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            view: self.view,
+            range: self.range.clone(),
+        }
+    }
+}
+
+impl<'a, V: View + ?Sized> AsaView<'a, V> {
+    /// Returns the range of indexes in the underlying view that the subtree covers
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the root node of the subtree
+    #[inline]
+    pub fn root(&self) -> &'a V::Node {
+        self.view.node(self.range.start)
+    }
+}
+
+impl<V: View + ?Sized> View for AsaView<'_, V> {
+    type Node = V::Node;
+
+    #[inline]
+    fn node(&self, idx: usize) -> &Self::Node {
+        assert!(idx < self.range.len(), "index out of bounds of the view");
+        self.view.node(self.range.start + idx)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    #[inline]
+    fn kind(&self, idx: usize) -> NodeKind {
+        assert!(idx < self.range.len(), "index out of bounds of the view");
+        self.view.kind(self.range.start + idx)
+    }
+
+    #[inline]
+    fn subtree_end(&self, idx: usize) -> usize {
+        assert!(idx < self.range.len(), "index out of bounds of the view");
+        // let the underlying view skip the subtree (in case it can do it faster)
+        self.view.subtree_end(self.range.start + idx) - self.range.start
+    }
+}
+
+/// A view of a **completed** ASA with a precomputed table of the lengths of every subtree, for skipping over subtrees in constant time
+///
+/// The table is built in one pass when the view is created, so the ASA should not be modified while the view exists
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct SkipView<'a, V: View + ?Sized> {
+    view: &'a V,
+    lengths: Box<[usize]>,
+}
+
+#[cfg(feature = "alloc")]
+impl<V: View + ?Sized> Clone for SkipView<'_, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            view: self.view,
+            lengths: self.lengths.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, V: View + ?Sized> SkipView<'a, V> {
+    /// Builds the subtree length table of a completed view
+    pub fn new(view: &'a V) -> Self {
+        let mut lengths = vec![0; view.len()].into_boxed_slice();
+
+        // walk backwards, so the lengths of the children of a node are always on the stack before the node itself (with the first child on top)
+        let mut stack = Vec::new();
+        for idx in (0..view.len()).rev() {
+            let arity = view.kind(idx).arity();
+            let length = 1 + stack.drain(stack.len() - arity..).sum::<usize>();
+            lengths[idx] = length;
+            stack.push(length);
+        }
+
+        Self { view, lengths }
+    }
+
+    /// Returns the length of the subtree of a node (including the node itself)
+    #[inline]
+    pub fn subtree_len(&self, idx: usize) -> usize {
+        self.lengths[idx]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<V: View + ?Sized> View for SkipView<'_, V> {
+    type Node = V::Node;
+
+    #[inline]
+    fn node(&self, idx: usize) -> &Self::Node {
+        self.view.node(idx)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.view.len()
+    }
+
+    #[inline]
+    fn kind(&self, idx: usize) -> NodeKind {
+        self.view.kind(idx)
+    }
+
+    #[inline]
+    fn subtree_end(&self, idx: usize) -> usize {
+        idx + self.lengths[idx]
+    }
+}
+
+/// An iterator over the indexes of the children of a node
+#[derive(Debug)]
+pub struct Children<'a, V: View + ?Sized> {
+    view: &'a V,
+    next: usize,
+    remaining: usize,
+}
+
+impl<V: View + ?Sized> Iterator for Children<'_, V> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        // skip over the subtree of the current child to get to the next one
+        let child = self.next;
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            self.next = self.view.subtree_end(child);
+        }
+
+        Some(child)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<V: View + ?Sized> ExactSizeIterator for Children<'_, V> {}
+
+/// An iterator over the index, depth and node of every node in a view
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct WithDepth<'a, V: View + ?Sized> {
+    view: &'a V,
+    idx: usize,
+    /// The amount of children left to walk for each of the ancestors of the current node
+    remaining: Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, V: View + ?Sized> Iterator for WithDepth<'a, V> {
+    type Item = (usize, usize, &'a V::Node);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.view.len() {
+            return None;
+        }
+
+        // pop off any ancestors that have had all of their children walked
+        while self.remaining.last() == Some(&0) {
+            self.remaining.pop();
+        }
+
+        let idx = self.idx;
+        let node = self.view.node(idx);
+        let depth = self.remaining.len();
+        self.idx += 1;
+        if let Some(remaining) = self.remaining.last_mut() {
+            *remaining -= 1;
+        }
+
+        // the following nodes are the children of this node
+        let arity = self.view.kind(idx).arity();
+        if arity > 0 {
+            self.remaining.push(arity);
+        }
+
+        Some((idx, depth, node))
+    }
+}
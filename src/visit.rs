@@ -0,0 +1,237 @@
+//! Visitors for walking (and evaluating) completed ASAs
+//!
+//! The walkers use an explicit stack instead of recursion, so that even extremely deep ASAs can't overflow the call stack;
+//! the children of a node are always visited (in order) before the node itself
+
+use alloc::vec::Vec;
+use core::convert::Infallible;
+use crate::{asa::ASA, node::{Node, NodeKind}, view::View};
+
+/// Visits the nodes of a completed ASA, building an output from the outputs of the children of each node
+///
+/// Every kind of node must be visitable, as the kinds of nodes in an ASA are only known at runtime; a visitor for nodes without (eg.) mixfix nodes can just mark that method as `unreachable!()`
+pub trait Visitor<N: Node> {
+    /// The output of visiting a node
+    type Output;
+
+    /// Visits an operand node
+    fn visit_operand(&mut self, node: &N) -> Self::Output;
+    /// Visits a unary node with the output of it's child
+    fn visit_unary(&mut self, node: &N, child: Self::Output) -> Self::Output;
+    /// Visits a binary node with the outputs of it's children
+    fn visit_binary(&mut self, node: &N, lhs: Self::Output, rhs: Self::Output) -> Self::Output;
+    /// Visits a mixfix node with the outputs of all of it's 'parameters' (in order)
+    fn visit_mixfix(&mut self, node: &N, params: Vec<Self::Output>) -> Self::Output;
+    /// Visits an n-ary node with the outputs of all of it's 'parameters' (in order)
+    fn visit_nary(&mut self, node: &N, params: Vec<Self::Output>) -> Self::Output;
+    /// Visits a postfix node with the output of it's operand and the outputs of all of it's arguments (in order)
+    fn visit_postfix(&mut self, node: &N, operand: Self::Output, args: Vec<Self::Output>) -> Self::Output;
+}
+
+/// Visits the nodes of a completed ASA like a `Visitor`, but where visiting a node can fail and stop the walk
+pub trait TryVisitor<N: Node> {
+    /// The output of visiting a node
+    type Output;
+    /// The error of visiting a node
+    type Error;
+
+    /// Visits an operand node
+    fn visit_operand(&mut self, node: &N) -> Result<Self::Output, Self::Error>;
+    /// Visits a unary node with the output of it's child
+    fn visit_unary(&mut self, node: &N, child: Self::Output) -> Result<Self::Output, Self::Error>;
+    /// Visits a binary node with the outputs of it's children
+    fn visit_binary(&mut self, node: &N, lhs: Self::Output, rhs: Self::Output) -> Result<Self::Output, Self::Error>;
+    /// Visits a mixfix node with the outputs of all of it's 'parameters' (in order)
+    fn visit_mixfix(&mut self, node: &N, params: Vec<Self::Output>) -> Result<Self::Output, Self::Error>;
+    /// Visits an n-ary node with the outputs of all of it's 'parameters' (in order)
+    fn visit_nary(&mut self, node: &N, params: Vec<Self::Output>) -> Result<Self::Output, Self::Error>;
+    /// Visits a postfix node with the output of it's operand and the outputs of all of it's arguments (in order)
+    fn visit_postfix(&mut self, node: &N, operand: Self::Output, args: Vec<Self::Output>) -> Result<Self::Output, Self::Error>;
+}
+
+/// Visits the nodes of a completed ASA like a `Visitor`, but with mutable access to each node
+pub trait VisitorMut<N: Node> {
+    /// The output of visiting a node
+    type Output;
+
+    /// Visits an operand node
+    fn visit_operand(&mut self, node: &mut N) -> Self::Output;
+    /// Visits a unary node with the output of it's child
+    fn visit_unary(&mut self, node: &mut N, child: Self::Output) -> Self::Output;
+    /// Visits a binary node with the outputs of it's children
+    fn visit_binary(&mut self, node: &mut N, lhs: Self::Output, rhs: Self::Output) -> Self::Output;
+    /// Visits a mixfix node with the outputs of all of it's 'parameters' (in order)
+    fn visit_mixfix(&mut self, node: &mut N, params: Vec<Self::Output>) -> Self::Output;
+    /// Visits an n-ary node with the outputs of all of it's 'parameters' (in order)
+    fn visit_nary(&mut self, node: &mut N, params: Vec<Self::Output>) -> Self::Output;
+    /// Visits a postfix node with the output of it's operand and the outputs of all of it's arguments (in order)
+    fn visit_postfix(&mut self, node: &mut N, operand: Self::Output, args: Vec<Self::Output>) -> Self::Output;
+}
+
+/// Turns a `Visitor` into a `TryVisitor` that never fails
+struct Infallibly<'a, V>(&'a mut V);
+
+impl<N: Node, V: Visitor<N>> TryVisitor<N> for Infallibly<'_, V> {
+    type Output = V::Output;
+    type Error = Infallible;
+
+    #[inline]
+    fn visit_operand(&mut self, node: &N) -> Result<Self::Output, Self::Error> {
+        Ok(self.0.visit_operand(node))
+    }
+
+    #[inline]
+    fn visit_unary(&mut self, node: &N, child: Self::Output) -> Result<Self::Output, Self::Error> {
+        Ok(self.0.visit_unary(node, child))
+    }
+
+    #[inline]
+    fn visit_binary(&mut self, node: &N, lhs: Self::Output, rhs: Self::Output) -> Result<Self::Output, Self::Error> {
+        Ok(self.0.visit_binary(node, lhs, rhs))
+    }
+
+    #[inline]
+    fn visit_mixfix(&mut self, node: &N, params: Vec<Self::Output>) -> Result<Self::Output, Self::Error> {
+        Ok(self.0.visit_mixfix(node, params))
+    }
+
+    #[inline]
+    fn visit_nary(&mut self, node: &N, params: Vec<Self::Output>) -> Result<Self::Output, Self::Error> {
+        Ok(self.0.visit_nary(node, params))
+    }
+
+    #[inline]
+    fn visit_postfix(&mut self, node: &N, operand: Self::Output, args: Vec<Self::Output>) -> Result<Self::Output, Self::Error> {
+        Ok(self.0.visit_postfix(node, operand, args))
+    }
+}
+
+/// Walks the nodes in `0..len` with an explicit stack, calling `visit` on every node once all of it's children have been visited
+///
+/// `visit` is given the outputs of every node that is still waiting to be used, with the outputs of the node's children on top
+fn walk_stack<C, O, E>(
+    ctx: &mut C,
+    len: usize,
+    arity: impl Fn(&C, usize) -> usize,
+    visit: impl Fn(&mut C, usize, &mut Vec<O>) -> Result<O, E>,
+) -> Result<O, E> {
+    let mut outputs = Vec::new();
+    // the nodes that are waiting on their children, alongside the amount of children they're still waiting on
+    let mut waiting: Vec<(usize, usize)> = Vec::new();
+
+    for idx in 0..len {
+        let arity = arity(ctx, idx);
+        if arity > 0 {
+            waiting.push((idx, arity));
+            continue;
+        }
+
+        // visit the node, and then every parent that was only waiting on it
+        let mut output = visit(ctx, idx, &mut outputs)?;
+        loop {
+            match waiting.last_mut() {
+                Some((_, remaining)) if *remaining > 1 => {
+                    *remaining -= 1;
+                    outputs.push(output);
+                    break;
+                },
+                Some(&mut (parent, _)) => {
+                    waiting.pop();
+                    outputs.push(output);
+                    output = visit(ctx, parent, &mut outputs)?;
+                },
+                None => return Ok(output),
+            }
+        }
+    }
+
+    panic!("cannot walk an incomplete ASA")
+}
+
+/// The outputs of the children of a node, in the form that the kind of node expects them
+enum Params<O> {
+    Operand,
+    Unary(O),
+    Binary(O, O),
+    Mixfix(Vec<O>),
+    Nary(Vec<O>),
+    Postfix(O, Vec<O>),
+}
+
+impl<O> Params<O> {
+    /// Pops the outputs of the children of a node off of the output stack
+    #[inline]
+    fn pop(kind: NodeKind, outputs: &mut Vec<O>) -> Self {
+        match kind {
+            NodeKind::Operand => Params::Operand,
+            NodeKind::Unary(_) => Params::Unary(outputs.pop().unwrap()),
+            NodeKind::Binary => {
+                let rhs = outputs.pop().unwrap();
+                let lhs = outputs.pop().unwrap();
+                Params::Binary(lhs, rhs)
+            },
+            NodeKind::Mixfix(_) => Params::Mixfix(outputs.split_off(outputs.len() - kind.arity())),
+            NodeKind::Nary(_) => Params::Nary(outputs.split_off(outputs.len() - kind.arity())),
+            NodeKind::Postfix(_) => {
+                let args = outputs.split_off(outputs.len() + 1 - kind.arity());
+                Params::Postfix(outputs.pop().unwrap(), args)
+            },
+        }
+    }
+}
+
+/// Walks a completed ASA (or view) with a visitor, returning the output of the root node (panics if the ASA is incomplete)
+#[inline]
+pub fn walk<V: View + ?Sized, Vis: Visitor<V::Node>>(view: &V, visitor: &mut Vis) -> Vis::Output {
+    match try_walk(view, &mut Infallibly(visitor)) {
+        Ok(output) => output,
+        Err(never) => match never {},
+    }
+}
+
+/// Walks a completed ASA (or view) with a fallible visitor, returning the output of the root node or the first error (panics if the ASA is incomplete)
+pub fn try_walk<V: View + ?Sized, Vis: TryVisitor<V::Node>>(view: &V, visitor: &mut Vis) -> Result<Vis::Output, Vis::Error> {
+    walk_stack(
+        &mut (view, visitor),
+        view.len(),
+        |(view, _), idx| view.kind(idx).arity(),
+        |(view, visitor), idx, outputs| {
+            let node = view.node(idx);
+            match Params::pop(view.kind(idx), outputs) {
+                Params::Operand => visitor.visit_operand(node),
+                Params::Unary(child) => visitor.visit_unary(node, child),
+                Params::Binary(lhs, rhs) => visitor.visit_binary(node, lhs, rhs),
+                Params::Mixfix(params) => visitor.visit_mixfix(node, params),
+                Params::Nary(params) => visitor.visit_nary(node, params),
+                Params::Postfix(operand, args) => visitor.visit_postfix(node, operand, args),
+            }
+        },
+    )
+}
+
+/// Walks a completed ASA with a visitor that can modify the nodes, returning the output of the root node (panics if the ASA is incomplete)
+pub fn walk_mut<A: ASA, Vis: VisitorMut<A::Node>>(asa: &mut A, visitor: &mut Vis) -> Vis::Output {
+    let len = asa.get_len();
+    let output = walk_stack(
+        &mut (asa, visitor),
+        len,
+        |(asa, _), idx| asa.get_kind(idx).arity(),
+        |(asa, visitor), idx, outputs| {
+            let kind = asa.get_kind(idx);
+            let node = asa.get_node_mut(idx);
+            Ok::<_, Infallible>(match Params::pop(kind, outputs) {
+                Params::Operand => visitor.visit_operand(node),
+                Params::Unary(child) => visitor.visit_unary(node, child),
+                Params::Binary(lhs, rhs) => visitor.visit_binary(node, lhs, rhs),
+                Params::Mixfix(params) => visitor.visit_mixfix(node, params),
+                Params::Nary(params) => visitor.visit_nary(node, params),
+                Params::Postfix(operand, args) => visitor.visit_postfix(node, operand, args),
+            })
+        },
+    );
+
+    match output {
+        Ok(output) => output,
+        Err(never) => match never {},
+    }
+}
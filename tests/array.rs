@@ -0,0 +1,87 @@
+use ketchup::{asa::{ArrayASA, ASA}, error::OwnedError, node::Node, parse, render};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[nary]
+    Tuple(usize),
+    #[mixfix(prec = 0, assoc = right)]
+    Ternary,
+    #[binary(prec = 1)]
+    Add,
+    #[binary(prec = 2)]
+    Mul,
+    #[unary(prec = 3)]
+    Neg,
+}
+
+type Asa<const N: usize, const S: usize = 0> = ArrayASA<MyNode, N, { MyNode::MAX_PRECEDENCE + 1 }, S>;
+
+#[test]
+fn parse() {
+    let mut asa = Asa::<8>::new(MyNode::MAX_PRECEDENCE);
+    for node in [MyNode::Neg, MyNode::Number(1), MyNode::Mul, MyNode::Number(2), MyNode::Add, MyNode::Number(3)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::ensure_completed(&mut asa).unwrap();
+
+    assert_eq!(
+        asa.nodes().cloned().collect::<Vec<_>>(),
+        [MyNode::Add, MyNode::Mul, MyNode::Neg, MyNode::Number(1), MyNode::Number(2), MyNode::Number(3)],
+    );
+    assert_eq!(format!("{:?}", render::sexpr(&asa)), "(Add (Mul (Neg Number(1)) Number(2)) Number(3))");
+}
+
+#[test]
+fn capacity_exceeded() {
+    let mut asa = Asa::<3>::new(MyNode::MAX_PRECEDENCE);
+    for node in [MyNode::Number(1), MyNode::Add, MyNode::Number(2)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    assert!(asa.is_full());
+
+    let error = parse::node(MyNode::Mul, &mut asa).unwrap_err().into_owned();
+    assert_eq!(error, OwnedError::CapacityExceeded(MyNode::Mul));
+
+    // the ASA is left untouched by the failed node
+    assert_eq!(asa.get_len(), 3);
+    parse::ensure_completed(&mut asa).unwrap();
+}
+
+#[test]
+fn scopes() {
+    let mut asa = Asa::<16, 2>::new(MyNode::MAX_PRECEDENCE);
+    for node in [MyNode::Number(1), MyNode::Add, MyNode::Tuple(0), MyNode::Number(2)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::separator(&mut asa).unwrap();
+    for node in [MyNode::Number(3), MyNode::Ternary, MyNode::Number(4)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::node(MyNode::Mul, &mut asa).unwrap();
+    parse::node(MyNode::Number(6), &mut asa).unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+
+    assert_eq!(
+        format!("{:?}", render::sexpr(&asa)),
+        "(Add Number(1) (Mul (Tuple(2) Number(2) (Ternary Number(3) Number(4) Number(5))) Number(6)))",
+    );
+}
+
+#[test]
+fn scopes_exceeded() {
+    let mut asa = Asa::<16, 1>::new(MyNode::MAX_PRECEDENCE);
+    parse::node(MyNode::Tuple(0), &mut asa).unwrap();
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    assert!(asa.scopes_full());
+
+    // there's room for the node, but not for the scope that it opens
+    let error = parse::node(MyNode::Ternary, &mut asa).unwrap_err().into_owned();
+    assert_eq!(error, OwnedError::CapacityExceeded(MyNode::Ternary));
+    let error = parse::node(MyNode::Tuple(0), &mut asa).unwrap_err().into_owned();
+    assert_eq!(error, OwnedError::UnexpectedNode(MyNode::Tuple(0)));
+}
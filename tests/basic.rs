@@ -1,4 +1,4 @@
-use ketchup::{asa::{VectorASA, ASA}, error::Error, node::{Node, NodeKind}, parse, Precedence};
+use ketchup::{asa::{VectorASA, ASA}, error::Error, node::{Alignment, Associativity, Node, NodeKind}, parse, Precedence};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum MyNode {
@@ -18,9 +18,9 @@ impl Node for MyNode {
     fn get_kind(&self) -> NodeKind {
         match self {
             MyNode::Number(_) => NodeKind::Operand,
-            MyNode::Call(_) => NodeKind::Unary,
-            MyNode::Pos => NodeKind::Unary,
-            MyNode::Neg => NodeKind::Unary,
+            MyNode::Call(_) => NodeKind::Unary(Alignment::Right),
+            MyNode::Pos => NodeKind::Unary(Alignment::Left),
+            MyNode::Neg => NodeKind::Unary(Alignment::Left),
             MyNode::Add => NodeKind::Binary,
             MyNode::Sub => NodeKind::Binary,
             MyNode::Mul => NodeKind::Binary,
@@ -197,3 +197,138 @@ fn mix_of_precedences() {
         MyNode::Number(4),
     ]);
 }
+
+#[test]
+fn mix_of_precedences_lower_after_higher() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // 1 * 2 + 3 * 4
+    parse::operand(MyNode::Number(1), &mut asa).unwrap();
+    parse::binary_node(MyNode::Mul, true, &mut asa).unwrap();
+    parse::operand(MyNode::Number(2), &mut asa).unwrap();
+    parse::binary_node(MyNode::Add, true, &mut asa).unwrap();
+    parse::operand(MyNode::Number(3), &mut asa).unwrap();
+    parse::binary_node(MyNode::Mul, true, &mut asa).unwrap();
+    parse::operand(MyNode::Number(4), &mut asa).unwrap();
+
+    assert!(*asa.is_complete());
+    assert_eq!(asa.vector[..], [
+        MyNode::Add,
+        MyNode::Mul,
+        MyNode::Number(1),
+        MyNode::Number(2),
+        MyNode::Mul,
+        MyNode::Number(3),
+        MyNode::Number(4),
+    ]);
+}
+
+#[test]
+fn lookuptable_after_lower_precedence() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // 1 * 2 + 3
+    parse::operand(MyNode::Number(1), &mut asa).unwrap();
+    parse::binary_node(MyNode::Mul, true, &mut asa).unwrap();
+    parse::operand(MyNode::Number(2), &mut asa).unwrap();
+    assert_eq!(asa.lookuptable(), [None, Some(0), None, None]);
+
+    // the `Mul` is now a part of the `Add`, so it's entry must be cleared rather than shifted
+    parse::binary_node(MyNode::Add, true, &mut asa).unwrap();
+    assert_eq!(asa.lookuptable(), [Some(0), None, None, None]);
+
+    parse::operand(MyNode::Number(3), &mut asa).unwrap();
+    assert_eq!(asa.vector[..], [MyNode::Add, MyNode::Mul, MyNode::Number(1), MyNode::Number(2), MyNode::Number(3)]);
+}
+
+#[test]
+fn alternating_precedences() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // 1 * 2 + 3 * 4 - 5 / 6
+    for node in [
+        MyNode::Number(1), MyNode::Mul, MyNode::Number(2),
+        MyNode::Add, MyNode::Number(3), MyNode::Mul, MyNode::Number(4),
+        MyNode::Sub, MyNode::Number(5), MyNode::Div, MyNode::Number(6),
+    ] {
+        parse::node(node, &mut asa).unwrap();
+    }
+
+    assert!(*asa.is_complete());
+    assert_eq!(asa.vector[..], [
+        MyNode::Sub,
+        MyNode::Add,
+        MyNode::Mul,
+        MyNode::Number(1),
+        MyNode::Number(2),
+        MyNode::Mul,
+        MyNode::Number(3),
+        MyNode::Number(4),
+        MyNode::Div,
+        MyNode::Number(5),
+        MyNode::Number(6),
+    ]);
+}
+
+#[test]
+fn repeated_unary_left_align() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // --1 * 2
+    parse::unary_left_align(MyNode::Neg, &mut asa).unwrap();
+    parse::unary_left_align(MyNode::Neg, &mut asa).unwrap();
+    parse::operand(MyNode::Number(1), &mut asa).unwrap();
+    parse::binary_node(MyNode::Mul, true, &mut asa).unwrap();
+    parse::operand(MyNode::Number(2), &mut asa).unwrap();
+
+    assert!(*asa.is_complete());
+    assert_eq!(asa.vector[..], [MyNode::Mul, MyNode::Neg, MyNode::Neg, MyNode::Number(1), MyNode::Number(2)]);
+}
+
+#[test]
+fn node_dispatch() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // -1 * 2 + 3(4)
+    for node in [
+        MyNode::Neg,
+        MyNode::Number(1),
+        MyNode::Mul,
+        MyNode::Number(2),
+        MyNode::Add,
+        MyNode::Number(3),
+        MyNode::Call(4),
+    ] {
+        parse::node(node, &mut asa).unwrap();
+    }
+
+    assert!(*asa.is_complete());
+    assert_eq!(asa.vector[..], [
+        MyNode::Add,
+        MyNode::Mul,
+        MyNode::Neg,
+        MyNode::Number(1),
+        MyNode::Number(2),
+        MyNode::Call(4),
+        MyNode::Number(3),
+    ]);
+
+    assert_eq!(MyNode::Add.get_associativity(), Associativity::Left);
+}
+
+#[test]
+fn node_dispatch_errors() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    let Err(Error::UnexpectedExpectedNode { oper: None, found: MyNode::Call(1) }) = parse::node(MyNode::Call(1), &mut asa)
+    else {
+        panic!("assert failed");
+    };
+
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+
+    let Err(Error::UnexpectedNode(MyNode::Neg)) = parse::node(MyNode::Neg, &mut asa)
+    else {
+        panic!("assert failed");
+    };
+}
@@ -0,0 +1,163 @@
+use ketchup::{asa::{ArrayASA, DequeASA, Removable, RopeASA, SoaASA, VectorASA, ASA}, checkpoint::Checkpointed, node::{Nary, Node, NodeKind}, parse};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[nary]
+    Tuple(usize),
+    #[mixfix(prec = 0, assoc = right)]
+    Ternary,
+    #[binary(prec = 1)]
+    Add,
+    #[binary(prec = 2)]
+    Mul,
+    #[unary(prec = 3, align = right)]
+    Call,
+}
+
+/// Parses some nodes into an ASA
+fn parsed<A: ASA<Node = MyNode>>(nodes: impl IntoIterator<Item = MyNode>) -> A {
+    let mut asa = A::new(MyNode::MAX_PRECEDENCE);
+    for node in nodes {
+        parse::node(node, &mut asa).unwrap();
+    }
+    asa
+}
+
+#[test]
+fn rollback_inserts() {
+    let mut asa = parsed::<Checkpointed<VectorASA<MyNode>>>([MyNode::Number(1), MyNode::Add, MyNode::Number(2)]);
+    let before = format!("{:?}", asa.inner());
+
+    // try `* 3 f` and then back off
+    let checkpoint = asa.checkpoint();
+    for node in [MyNode::Mul, MyNode::Number(3), MyNode::Call, MyNode::Add, MyNode::Number(4)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    asa.rollback(checkpoint);
+    assert_eq!(format!("{:?}", asa.inner()), before);
+
+    // parsing carries on like nothing happened
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    let expected = parsed::<VectorASA<MyNode>>([MyNode::Number(1), MyNode::Add, MyNode::Number(2), MyNode::Add, MyNode::Number(5)]);
+    assert_eq!(format!("{:?}", asa.into_inner()), format!("{expected:?}"));
+}
+
+#[test]
+fn rollback_scopes() {
+    let mut asa = parsed::<Checkpointed<DequeASA<MyNode>>>([MyNode::Number(1), MyNode::Add, MyNode::Tuple(0), MyNode::Number(2)]);
+    let outer = asa.checkpoint();
+    let before_outer = format!("{:?}", asa.inner());
+
+    // close the tuple, and then roll back to reopen it
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    let inner = asa.checkpoint();
+    let before_inner = format!("{:?}", asa.inner());
+    parse::close(&mut asa).unwrap();
+    parse::node(MyNode::Mul, &mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    asa.rollback(inner);
+    assert_eq!(format!("{:?}", asa.inner()), before_inner);
+
+    // open and close a ternary, and then roll back past everything
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(6), &mut asa).unwrap();
+    asa.rollback(outer);
+    assert_eq!(format!("{:?}", asa.inner()), before_outer);
+    asa.commit();
+
+    parse::close(&mut asa).unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.inner().deque, [MyNode::Add, MyNode::Number(1), MyNode::Tuple(1), MyNode::Number(2)]);
+}
+
+/// Parses a long chain, rolls half of it back and then checks that the ASA is the same as one that only parsed the first half
+fn rollback_chain<A: Removable<Node = MyNode>>(len: i32) {
+    let chain = |len| (0..len).flat_map(|i| [MyNode::Number(i), if i % 2 == 0 { MyNode::Add } else { MyNode::Mul }]);
+
+    let mut asa = parsed::<Checkpointed<A>>(chain(len / 2));
+    let checkpoint = asa.checkpoint();
+    for node in chain(len).skip(len as usize) {
+        parse::node(node, &mut asa).unwrap();
+    }
+    asa.rollback(checkpoint);
+
+    let expected = parsed::<A>(chain(len / 2));
+    assert_eq!(asa.get_len(), expected.get_len());
+    assert!((0..expected.get_len()).all(|idx| asa.get_node(idx) == expected.get_node(idx)));
+}
+
+#[test]
+fn rollback_storages() {
+    rollback_chain::<RopeASA<MyNode>>(10_000);
+    rollback_chain::<SoaASA<MyNode>>(1_000);
+    rollback_chain::<ArrayASA<MyNode, 256, { MyNode::MAX_PRECEDENCE + 1 }>>(100);
+}
+
+#[test]
+fn rollback_cached_kind() {
+    // `(1, 2` with a checkpoint before the tuple is closed
+    let mut asa = parsed::<Checkpointed<SoaASA<MyNode>>>([MyNode::Tuple(0), MyNode::Number(1)]);
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    let checkpoint = asa.checkpoint();
+
+    // closing the tuple sets it's arity, which the `SoaASA` caches
+    parse::close(&mut asa).unwrap();
+    assert!(matches!(asa.get_kind(0), NodeKind::Nary(Nary { arity: 2, .. })));
+
+    // so rolling back has to restore the cached kind alongside the node
+    asa.rollback(checkpoint);
+    assert_eq!(asa.get_node(0), &MyNode::Tuple(0));
+    assert!(matches!(asa.get_kind(0), NodeKind::Nary(Nary { arity: 0, .. })));
+}
+
+#[test]
+fn rollback_across_scopes() {
+    // `1 * (2, 3` with a checkpoint inside of the tuple
+    let mut asa = parsed::<Checkpointed<VectorASA<MyNode>>>([MyNode::Number(1), MyNode::Mul, MyNode::Tuple(0), MyNode::Number(2)]);
+    parse::separator(&mut asa).unwrap();
+    let checkpoint = asa.checkpoint();
+    let before = format!("{:?}", asa.inner());
+
+    // close the tuple, and then change the lookup-table from outside of it before rolling back
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    for node in [MyNode::Add, MyNode::Number(4), MyNode::Mul, MyNode::Number(5), MyNode::Ternary, MyNode::Number(6)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    asa.rollback(checkpoint);
+    assert_eq!(format!("{:?}", asa.inner()), before);
+
+    // the outer lookup-table saved with the tuple's scope was restored too
+    parse::node(MyNode::Number(7), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Number(8), &mut asa).unwrap();
+    asa.commit();
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.into_inner().vector, [
+        MyNode::Add,
+            MyNode::Mul, MyNode::Number(1), MyNode::Tuple(2), MyNode::Number(2), MyNode::Number(7),
+            MyNode::Number(8),
+    ]);
+}
+
+#[test]
+fn recorded_once() {
+    // the parsing state is only recorded once per checkpoint, instead of on every access
+    let mut asa = parsed::<Checkpointed<VectorASA<MyNode>>>([MyNode::Number(0)]);
+    asa.checkpoint();
+    let before = asa.recorded();
+    for i in 1..1_000 {
+        parse::node(MyNode::Add, &mut asa).unwrap();
+        parse::node(MyNode::Number(i), &mut asa).unwrap();
+    }
+    // one insertion per node, and the parsing state once
+    assert_eq!(asa.recorded() - before, 2_000 - 2 + 4);
+}
@@ -0,0 +1,66 @@
+//! The nodes and expressions shared by the tests of the different implementations of ASA
+#![allow(dead_code)]
+
+use ketchup::{asa::ASA, node::Node, parse};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+pub enum MyNode {
+    #[operand]
+    Number(i32),
+    #[nary]
+    Tuple(usize),
+    #[mixfix(prec = 0, assoc = right)]
+    Ternary,
+    #[binary(prec = 1)]
+    Add,
+    #[binary(prec = 2)]
+    Mul,
+    #[unary(prec = 3)]
+    Neg,
+    #[unary(prec = 4, align = right)]
+    Call(u8),
+}
+
+/// Parses `-1 * 2 + (3, 4 ? 5 : 6) + 7 f` into any ASA
+pub fn example<A: ASA<Node = MyNode>>() -> A {
+    let mut asa = A::new(MyNode::MAX_PRECEDENCE);
+    for node in [MyNode::Neg, MyNode::Number(1), MyNode::Mul, MyNode::Number(2), MyNode::Add, MyNode::Tuple(0), MyNode::Number(3)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(6), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    for node in [MyNode::Add, MyNode::Number(7), MyNode::Call(0)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::ensure_completed(&mut asa).unwrap();
+    asa
+}
+
+/// Parses `(3, 4 ? 5 : 6) + 1 + 2 + ... + n * 1 * 2 * ... * n` into any ASA, so that every `Mul` gets inserted into the middle of it
+pub fn chain<A: ASA<Node = MyNode>>(n: i32) -> A {
+    let mut asa = A::new(MyNode::MAX_PRECEDENCE);
+    for node in [MyNode::Tuple(0), MyNode::Number(3)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(6), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+
+    for oper in [MyNode::Add, MyNode::Mul] {
+        for i in 1..=n {
+            parse::node(oper.clone(), &mut asa).unwrap();
+            parse::node(MyNode::Number(i), &mut asa).unwrap();
+        }
+    }
+    parse::ensure_completed(&mut asa).unwrap();
+    asa
+}
@@ -0,0 +1,63 @@
+use ketchup::{asa::ASA, compact::{Compact, CompactASA, Interner, Operator, Operators}, node::{Alignment, Associativity, Mixfix, Node}, parse, render};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Maths;
+
+const NUMBER: u16 = 0;
+const TUPLE: u16 = 1;
+const TERNARY: u16 = 2;
+const ADD: u16 = 3;
+const MUL: u16 = 4;
+const POW: u16 = 5;
+const NEG: u16 = 6;
+
+impl Operators for Maths {
+    const OPERATORS: &'static [Operator] = &[
+        Operator::operand(),
+        Operator::nary(false),
+        Operator::mixfix(Mixfix::TERNARY, 0).assoc(Associativity::Right),
+        Operator::binary(1),
+        Operator::binary(2),
+        Operator::binary(3).assoc(Associativity::Right),
+        Operator::unary(Alignment::Left, 4),
+    ];
+}
+
+#[test]
+fn size() {
+    assert_eq!(core::mem::size_of::<Compact<Maths>>(), 8);
+    assert_eq!(Compact::<Maths>::MAX_PRECEDENCE, 4);
+}
+
+#[test]
+fn parse() {
+    let mut literals = Interner::new();
+    let mut number = |literal: &str| Compact::<Maths>::new(NUMBER, literals.intern(literal.to_string()));
+
+    // parses `-2 ^ 3 ^ 2 * (2, 1 ? 2 : 3) + 1`
+    let mut asa = CompactASA::<Maths>::new(Compact::<Maths>::MAX_PRECEDENCE);
+    for node in [Compact::operator(NEG), number("2"), Compact::operator(POW), number("3"), Compact::operator(POW), number("2"), Compact::operator(MUL), Compact::operator(TUPLE), number("2")] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::separator(&mut asa).unwrap();
+    for node in [number("1"), Compact::operator(TERNARY), number("2")] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::separator(&mut asa).unwrap();
+    parse::node(number("3"), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::node(Compact::operator(ADD), &mut asa).unwrap();
+    parse::node(number("1"), &mut asa).unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+
+    // equal literals share a payload
+    assert_eq!(literals.len(), 3);
+
+    let types = asa.vector.iter().map(|node| node.node_type).collect::<Vec<_>>();
+    assert_eq!(types, [ADD, MUL, POW, NEG, NUMBER, POW, NUMBER, NUMBER, TUPLE, NUMBER, TERNARY, NUMBER, NUMBER, NUMBER, NUMBER]);
+    assert_eq!(asa.vector[8].payload, 2); // the arity of the tuple
+    assert_eq!(literals.get(asa.vector[4].payload), "2");
+
+    let sexpr = format!("{:?}", render::sexpr(&asa));
+    assert_eq!(sexpr.matches('(').count(), 7);
+}
@@ -0,0 +1,30 @@
+use ketchup::{asa::{DequeASA, VectorASA, ASA}, node::Node, parse};
+
+mod common;
+
+use common::{example, MyNode};
+
+#[test]
+fn matches_vector() {
+    let vector = example::<VectorASA<MyNode>>();
+    let deque = example::<DequeASA<MyNode>>();
+
+    assert_eq!(deque.deque.iter().collect::<Vec<_>>(), vector.vector.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn left_associative_chain() {
+    // every `Add` gets inserted at the very start of the ASA
+    let mut asa = DequeASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::node(MyNode::Number(0), &mut asa).unwrap();
+    for i in 1..=1000 {
+        parse::node(MyNode::Add, &mut asa).unwrap();
+        parse::node(MyNode::Number(i), &mut asa).unwrap();
+    }
+    parse::ensure_completed(&mut asa).unwrap();
+
+    assert_eq!(asa.get_len(), 2001);
+    assert!(asa.deque.iter().take(1000).all(|node| *node == MyNode::Add));
+    assert_eq!(asa.deque[1000], MyNode::Number(0));
+    assert_eq!(asa.deque[2000], MyNode::Number(1000));
+}
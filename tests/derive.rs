@@ -0,0 +1,148 @@
+use ketchup::{asa::{VectorASA, ASA}, node::{Alignment, Associativity, Node, NodeKind}, parse};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[operand]
+    Ident { name: String },
+    #[binary(prec = 0)]
+    Add,
+    #[binary(prec = 1, assoc = left)]
+    Mul,
+    #[unary(prec = 2, align = left)]
+    Neg,
+    #[binary(prec = 3, assoc = right)]
+    Pow,
+    #[unary(prec = 4, align = right)]
+    Call(i32),
+}
+
+#[derive(Debug, Clone, Node)]
+enum Generic<T: std::fmt::Debug + Clone> {
+    #[operand]
+    Value(T),
+    #[binary(prec = 0)]
+    Pair,
+}
+
+/// Precedences copied from C's operator precedence table (where smaller binds tighter, so they're negated)
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum Sparse {
+    #[operand]
+    Number(i32),
+    #[binary(prec = -12)]
+    Or,
+    #[binary(prec = -4)]
+    Add,
+    #[binary(prec = -4)]
+    Sub,
+    #[binary(prec = -3)]
+    Mul,
+    #[unary(prec = -2)]
+    Neg,
+    #[postfix(prec = -1)]
+    Call(usize),
+}
+
+#[derive(Debug, Clone, Node)]
+enum OnlyOperands {
+    #[operand]
+    Number,
+}
+
+#[test]
+fn max_precedence() {
+    assert_eq!(MyNode::MAX_PRECEDENCE, 4);
+    assert_eq!(Generic::<u8>::MAX_PRECEDENCE, 0);
+    assert_eq!(OnlyOperands::MAX_PRECEDENCE, 0);
+    assert_eq!(OnlyOperands::Number.get_kind(), NodeKind::Operand);
+}
+
+#[test]
+fn kinds_and_precedences() {
+    assert_eq!(MyNode::Number(1).get_kind(), NodeKind::Operand);
+    assert_eq!(MyNode::Ident { name: "x".to_string() }.get_kind(), NodeKind::Operand);
+    assert_eq!(MyNode::Neg.get_kind(), NodeKind::Unary(Alignment::Left));
+    assert_eq!(MyNode::Call(1).get_kind(), NodeKind::Unary(Alignment::Right));
+    assert_eq!(MyNode::Add.get_kind(), NodeKind::Binary);
+    assert_eq!(Generic::Value(1).get_kind(), NodeKind::Operand);
+    assert_eq!(Generic::<u8>::Pair.get_kind(), NodeKind::Binary);
+
+    assert_eq!(MyNode::Add.get_precedence(), 0);
+    assert_eq!(MyNode::Mul.get_precedence(), 1);
+    assert_eq!(MyNode::Neg.get_precedence(), 2);
+    assert_eq!(MyNode::Call(1).get_precedence(), 4);
+
+    assert_eq!(MyNode::Add.get_associativity(), Associativity::Left);
+    assert_eq!(MyNode::Pow.get_associativity(), Associativity::Right);
+    assert_eq!(MyNode::Call(1).get_associativity(), Associativity::Left);
+}
+
+#[test]
+#[should_panic]
+fn operand_precedence() {
+    MyNode::Number(1).get_precedence();
+}
+
+#[test]
+fn parse_derived() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // -1 + 2 ^ 3 ^ 4(5)
+    for node in [
+        MyNode::Neg,
+        MyNode::Number(1),
+        MyNode::Add,
+        MyNode::Number(2),
+        MyNode::Pow,
+        MyNode::Number(3),
+        MyNode::Pow,
+        MyNode::Number(4),
+        MyNode::Call(5),
+    ] {
+        parse::node(node, &mut asa).unwrap();
+    }
+
+    assert!(*asa.is_complete());
+    assert_eq!(asa.vector[..], [
+        MyNode::Add,
+        MyNode::Neg,
+        MyNode::Number(1),
+        MyNode::Pow,
+        MyNode::Number(2),
+        MyNode::Pow,
+        MyNode::Number(3),
+        MyNode::Call(5),
+        MyNode::Number(4),
+    ]);
+}
+
+#[test]
+fn sparse_precedences() {
+    assert_eq!(Sparse::MAX_PRECEDENCE, 4);
+    assert_eq!(Sparse::Or.get_precedence(), 0);
+    assert_eq!(Sparse::Add.get_precedence(), 1);
+    assert_eq!(Sparse::Sub.get_precedence(), 1);
+    assert_eq!(Sparse::Mul.get_precedence(), 2);
+    assert_eq!(Sparse::Neg.get_precedence(), 3);
+    assert_eq!(Sparse::Call(0).get_precedence(), 4);
+
+    // 1 || -2 * 3 - 4
+    let mut asa = VectorASA::<Sparse>::new(Sparse::MAX_PRECEDENCE);
+    for node in [Sparse::Number(1), Sparse::Or, Sparse::Neg, Sparse::Number(2), Sparse::Mul, Sparse::Number(3), Sparse::Sub, Sparse::Number(4)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.lookuptable().len(), 5);
+    assert_eq!(asa.vector, [
+        Sparse::Or,
+        Sparse::Number(1),
+        Sparse::Sub,
+        Sparse::Mul,
+        Sparse::Neg,
+        Sparse::Number(2),
+        Sparse::Number(3),
+        Sparse::Number(4),
+    ]);
+}
@@ -0,0 +1,100 @@
+use ketchup::{asa::{VectorASA, ASA}, dot, node::Node, parse};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[operand]
+    Str(&'static str),
+    #[nary]
+    Tuple(usize),
+    #[mixfix(prec = 0, assoc = right)]
+    Ternary,
+    #[binary(prec = 1)]
+    Add,
+    #[unary(prec = 2)]
+    Neg,
+}
+
+#[test]
+fn export() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::nodes([MyNode::Number(1), MyNode::Add, MyNode::Neg, MyNode::Number(2)], &mut asa).unwrap();
+
+    assert_eq!(dot::export(&asa, &dot::Options::default()), "\
+digraph asa {
+    n0 [label=\"Add\", style=filled, fillcolor=lightsalmon];
+    n1 [label=\"Number(1)\", style=filled, fillcolor=lightblue];
+    n0 -> n1 [label=\"lhs\"];
+    n2 [label=\"Neg\", style=filled, fillcolor=khaki];
+    n0 -> n2 [label=\"rhs\"];
+    n3 [label=\"Number(2)\", style=filled, fillcolor=lightblue];
+    n2 -> n3;
+}
+");
+}
+
+#[test]
+fn export_with_indices() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // "a" ? ("b",) : 1
+    parse::node(MyNode::Str("a"), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Tuple(0), &mut asa).unwrap();
+    parse::node(MyNode::Str("b"), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+
+    let dot = dot::export(&asa, &dot::Options { indices: true });
+    assert!(dot.contains("n0 [label=\"0: Ternary\", style=filled, fillcolor=plum];"));
+    assert!(dot.contains("n1 [label=\"1: Str(\\\"a\\\")\", style=filled, fillcolor=lightblue];"));
+    assert!(dot.contains("n0 -> n1 [label=\"lhs\"];"));
+    assert!(dot.contains("n2 [label=\"2: Tuple(1)\", style=filled, fillcolor=palegreen];"));
+    assert!(dot.contains("n0 -> n2 [label=\"hole 0\"];"));
+    assert!(dot.contains("n2 -> n3 [label=\"0\"];"));
+    assert!(dot.contains("n0 -> n4 [label=\"rhs\"];"));
+}
+
+#[test]
+fn trace() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    let mut trace = dot::Trace::new(dot::Options::default());
+
+    for node in [MyNode::Number(1), MyNode::Add, MyNode::Number(2)] {
+        let label = format!("{node:?}");
+        parse::node(node, &mut asa).unwrap();
+        trace.step(&label, &asa);
+    }
+
+    let dot = trace.export();
+    assert!(dot.starts_with("digraph trace {\n    subgraph cluster_0 {\n        label=\"Number(1)\";\n        s0_n0 [label=\"Number(1)\""));
+    assert!(dot.contains("subgraph cluster_1 {\n        label=\"Add\";\n        s1_n0 [label=\"Add\""));
+    assert!(dot.contains("        s1_n0 -> s1_n1 [label=\"lhs\"];\n    }\n"));
+    assert!(dot.contains("        s2_n0 -> s2_n2 [label=\"rhs\"];\n    }\n}\n"));
+}
+
+#[test]
+fn trace_open_scopes() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    let mut trace = dot::Trace::new(dot::Options::default());
+
+    // 1 + (2, 3)
+    for node in [MyNode::Number(1), MyNode::Add, MyNode::Tuple(0), MyNode::Number(2)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    trace.step_asa("2", &mut asa);
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    trace.step_asa("3", &mut asa);
+    parse::close(&mut asa).unwrap();
+    trace.step_asa("close", &mut asa);
+
+    // the n-ary node is marked with the slot being filled until it's closed
+    let dot = trace.export();
+    assert!(dot.contains("s0_n2 [label=\"Tuple(0) (open, slot 0)\", style=\"filled,dashed\", fillcolor=palegreen];"));
+    assert!(dot.contains("s1_n2 [label=\"Tuple(0) (open, slot 1)\", style=\"filled,dashed\", fillcolor=palegreen];"));
+    assert!(dot.contains("s2_n2 [label=\"Tuple(2)\", style=filled, fillcolor=palegreen];"));
+}
@@ -0,0 +1,98 @@
+use std::cell::Cell;
+use ketchup::{asa::{RopeASA, VectorASA, ASA}, node::Node, parse};
+
+thread_local! {
+    /// The amount of nodes cloned on this thread
+    static CLONES: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Debug, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[binary(prec = 0)]
+    Add,
+    #[binary(prec = 1)]
+    Mul,
+    #[unary(prec = 2, align = right)]
+    Fact,
+}
+
+impl Clone for MyNode {
+    fn clone(&self) -> Self {
+        CLONES.with(|clones| clones.set(clones.get() + 1));
+        match self {
+            Self::Number(x) => Self::Number(*x),
+            Self::Add => Self::Add,
+            Self::Mul => Self::Mul,
+            Self::Fact => Self::Fact,
+        }
+    }
+}
+
+fn clones() -> usize {
+    CLONES.with(Cell::get)
+}
+
+/// Parses `0 + 1 * 2 + 3 * ... * n` onto any ASA
+fn chain<A: ASA<Node = MyNode>>(asa: &mut A, n: i32) {
+    parse::node(MyNode::Number(0), asa).unwrap();
+    for i in 1..n {
+        parse::node(if i % 2 == 0 { MyNode::Mul } else { MyNode::Add }, asa).unwrap();
+        parse::node(MyNode::Number(i), asa).unwrap();
+    }
+}
+
+fn parsed<A: ASA<Node = MyNode>>(n: i32, rest: impl IntoIterator<Item = MyNode>) -> Vec<MyNode> {
+    let mut asa = A::new(MyNode::MAX_PRECEDENCE);
+    chain(&mut asa, n);
+    for node in rest {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::ensure_completed(&mut asa).unwrap();
+    (0..asa.get_len()).map(|idx| asa.get_node(idx).clone()).collect()
+}
+
+#[test]
+fn fork_without_copying() {
+    let mut postfix = RopeASA::new(MyNode::MAX_PRECEDENCE);
+    chain(&mut postfix, 50_000);
+    assert_eq!(postfix.get_len(), 99_999);
+
+    // forking only copies the root of the rope
+    let before = clones();
+    let mut binary = postfix.fork();
+    assert!(clones() - before <= 64);
+
+    // the same token is parsed as a postfix node in one branch and a binary node in the other
+    let before = clones();
+    parse::node(MyNode::Fact, &mut postfix).unwrap();
+    parse::node(MyNode::Mul, &mut binary).unwrap();
+    parse::node(MyNode::Number(-1), &mut binary).unwrap();
+    assert!(clones() - before < 1_000, "parsing onto a fork copied {} nodes", clones() - before);
+
+    // each branch keeps it's own parsing state
+    parse::ensure_completed(&mut postfix).unwrap();
+    parse::ensure_completed(&mut binary).unwrap();
+    assert_eq!(postfix.into_vec(), parsed::<VectorASA<MyNode>>(50_000, [MyNode::Fact]));
+    assert_eq!(VectorASA::from(binary).vector, parsed::<VectorASA<MyNode>>(50_000, [MyNode::Mul, MyNode::Number(-1)]));
+}
+
+#[test]
+fn fork_incomplete() {
+    // fork while waiting for an operand
+    let mut left = RopeASA::new(MyNode::MAX_PRECEDENCE);
+    chain(&mut left, 1_000);
+    parse::node(MyNode::Add, &mut left).unwrap();
+    let mut right = left.fork();
+
+    parse::node(MyNode::Number(-1), &mut left).unwrap();
+    assert!(parse::ensure_completed(&mut right).is_err());
+    parse::node(MyNode::Number(-2), &mut right).unwrap();
+    parse::node(MyNode::Fact, &mut right).unwrap();
+
+    parse::ensure_completed(&mut left).unwrap();
+    parse::ensure_completed(&mut right).unwrap();
+    assert_eq!(left.into_vec(), parsed::<VectorASA<MyNode>>(1_000, [MyNode::Add, MyNode::Number(-1)]));
+    assert_eq!(right.into_vec(), parsed::<VectorASA<MyNode>>(1_000, [MyNode::Add, MyNode::Number(-2), MyNode::Fact]));
+}
@@ -0,0 +1,150 @@
+use ketchup::{asa::{ArrayASA, VectorASA, ASA}, error::{Error, Indexed, IndexedError}, node::Node, parse::{self, Recovering}};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[operand]
+    Error,
+    #[nary]
+    Tuple(usize),
+    #[binary(prec = 0)]
+    Add,
+    #[binary(prec = 1)]
+    Mul,
+    #[unary(prec = 2)]
+    Neg,
+    #[binary(prec = 3, assoc = right)]
+    Pow,
+}
+
+/// A token of the tests' 'language', where groups and n-ary nodes are delimited by separate tokens
+#[derive(Debug, Clone)]
+enum Token {
+    Node(MyNode),
+    Open,
+    Close,
+    Separator,
+    CloseTuple,
+}
+
+use MyNode::*;
+use Token::{Close as C, Open as O};
+
+fn n(node: MyNode) -> Token {
+    Token::Node(node)
+}
+
+/// Parses tokens into an ASA, stopping at the first error
+fn parse_tokens<A: ASA<Node = MyNode>>(tokens: impl IntoIterator<Item = Token>, asa: &mut A) -> Result<(), IndexedError<MyNode>> {
+    for token in tokens {
+        parse::indexed(asa, |asa| match token {
+            Token::Node(node) => parse::node(node, asa),
+            Token::Open => parse::open_group(asa),
+            Token::Close => parse::close_group(asa),
+            Token::Separator => parse::separator(asa),
+            Token::CloseTuple => parse::close(asa),
+        })?;
+    }
+    parse::indexed(asa, parse::ensure_completed)
+}
+
+fn parsed(tokens: impl IntoIterator<Item = Token>) -> Vec<MyNode> {
+    let mut asa = VectorASA::new(MyNode::MAX_PRECEDENCE);
+    parse_tokens(tokens, &mut asa).unwrap();
+    asa.vector
+}
+
+fn error(tokens: impl IntoIterator<Item = Token>) -> IndexedError<MyNode> {
+    let mut asa = VectorASA::new(MyNode::MAX_PRECEDENCE);
+    parse_tokens(tokens, &mut asa).unwrap_err()
+}
+
+#[test]
+fn groups() {
+    // (1 + 2) * 3
+    assert_eq!(parsed([O, n(Number(1)), n(Add), n(Number(2)), C, n(Mul), n(Number(3))]), [Mul, Add, Number(1), Number(2), Number(3)]);
+
+    // 1 * (2 + 3) * 4
+    assert_eq!(
+        parsed([n(Number(1)), n(Mul), O, n(Number(2)), n(Add), n(Number(3)), C, n(Mul), n(Number(4))]),
+        [Mul, Mul, Number(1), Add, Number(2), Number(3), Number(4)],
+    );
+
+    // 1 + (2) ^ 3 (the group is taken as an operand by nodes of greater precedence)
+    assert_eq!(parsed([n(Number(1)), n(Add), O, n(Number(2)), C, n(Pow), n(Number(3))]), [Add, Number(1), Pow, Number(2), Number(3)]);
+
+    // -(1 + 2) ^ (3 ^ 4)
+    assert_eq!(
+        parsed([n(Neg), O, n(Number(1)), n(Add), n(Number(2)), C, n(Pow), O, n(Number(3)), n(Pow), n(Number(4)), C]),
+        [Neg, Pow, Add, Number(1), Number(2), Pow, Number(3), Number(4)],
+    );
+
+    // ((1 + 2)) * ((3))
+    assert_eq!(
+        parsed([O, O, n(Number(1)), n(Add), n(Number(2)), C, C, n(Mul), O, O, n(Number(3)), C, C]),
+        [Mul, Add, Number(1), Number(2), Number(3)],
+    );
+
+    // (1, ((2, 3)) * 4)
+    assert_eq!(
+        parsed([n(Tuple(0)), n(Number(1)), Token::Separator, O, n(Tuple(0)), n(Number(2)), Token::Separator, n(Number(3)), Token::CloseTuple, C, n(Mul), n(Number(4)), Token::CloseTuple]),
+        [Tuple(2), Number(1), Mul, Tuple(2), Number(2), Number(3), Number(4)],
+    );
+}
+
+#[test]
+fn unbalanced_groups() {
+    assert_eq!(error([n(Number(1)), O, n(Number(2)), C]), IndexedError::UnexpectedGroup);
+    assert_eq!(error([n(Number(1)), C]), IndexedError::UnexpectedCloseGroup);
+    assert_eq!(error([O, n(Number(1)), n(Add), O, n(Number(2)), C]), IndexedError::ExpectedCloseGroup);
+    assert_eq!(error([O, n(Number(1)), C, C]), IndexedError::UnexpectedCloseGroup);
+
+    // the contents of the group must be complete
+    assert_eq!(error([n(Number(1)), n(Add), O, C]), IndexedError::EmptyExpression);
+    assert_eq!(error([O, n(Number(1)), n(Mul), C]), IndexedError::ExpectedNode(Indexed { idx: 0, node: Mul }));
+
+    // groups and n-ary nodes must be closed in order
+    assert_eq!(error([O, n(Tuple(0)), n(Number(1)), C]), IndexedError::ExpectedClose(Indexed { idx: 0, node: Tuple(0) }));
+    assert_eq!(error([n(Tuple(0)), O, n(Number(1)), Token::CloseTuple]), IndexedError::ExpectedCloseGroup);
+    assert_eq!(error([n(Tuple(0)), O, n(Number(1)), Token::Separator]), IndexedError::UnexpectedSeparator);
+
+    // a group has no room in an ASA without room for scopes
+    let mut asa = ArrayASA::<MyNode, 8, { MyNode::MAX_PRECEDENCE + 1 }>::new(MyNode::MAX_PRECEDENCE);
+    assert!(matches!(parse::open_group(&mut asa), Err(Error::GroupCapacityExceeded)));
+    assert_eq!(asa.get_len(), 0);
+}
+
+#[test]
+fn recover_groups() {
+    let mut asa = VectorASA::new(MyNode::MAX_PRECEDENCE);
+
+    // ((1 + ) * 2 ) ) (
+    let mut recovering = Recovering::new(&mut asa, || Error);
+    recovering.open_group();
+    recovering.open_group();
+    recovering.nodes([Number(1), Add]);
+    recovering.close_group();
+    recovering.nodes([Mul, Number(2)]);
+    recovering.close_group();
+    recovering.close_group();
+    recovering.open_group();
+    let errors = recovering.finish();
+
+    assert_eq!(errors, [
+        IndexedError::ExpectedNode(Indexed { idx: 0, node: Add }),
+        IndexedError::UnexpectedCloseGroup,
+        IndexedError::UnexpectedGroup,
+    ]);
+    assert_eq!(asa.vector, [Mul, Add, Number(1), Error, Number(2)]);
+
+    // (1 + (2
+    let mut asa = VectorASA::new(MyNode::MAX_PRECEDENCE);
+    let mut recovering = Recovering::new(&mut asa, || Error);
+    recovering.open_group();
+    recovering.nodes([Number(1), Add]);
+    recovering.open_group();
+    recovering.node(Number(2));
+    assert_eq!(recovering.finish(), [IndexedError::ExpectedCloseGroup, IndexedError::ExpectedCloseGroup]);
+    assert_eq!(asa.vector, [Add, Number(1), Number(2)]);
+}
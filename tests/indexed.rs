@@ -0,0 +1,85 @@
+use ketchup::{asa::{VectorASA, ASA}, error::{Indexed, IndexedError, OwnedError}, node::Node, parse};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[nary]
+    Tuple(usize),
+    #[binary(prec = 0)]
+    Add,
+    #[binary(prec = 1)]
+    Mul,
+}
+
+fn asa() -> VectorASA<MyNode> {
+    VectorASA::new(MyNode::MAX_PRECEDENCE)
+}
+
+#[test]
+fn empty_expression() {
+    let mut asa = asa();
+    assert_eq!(parse::indexed(&mut asa, parse::ensure_completed), Err(IndexedError::EmptyExpression));
+
+    let error = parse::indexed(&mut asa, |asa| parse::node(MyNode::Add, asa)).unwrap_err();
+    assert_eq!(error, IndexedError::UnexpectedExpectedNode { oper: None, found: MyNode::Add });
+}
+
+#[test]
+fn records_indexes() {
+    let mut asa = asa();
+    parse::nodes([MyNode::Number(1), MyNode::Add, MyNode::Number(2)], &mut asa).unwrap();
+    parse::node(MyNode::Mul, &mut asa).unwrap();
+
+    // the incomplete `Mul` was inserted at index 2
+    let error = parse::indexed(&mut asa, |asa| parse::node(MyNode::Add, asa)).unwrap_err();
+    assert_eq!(error, IndexedError::UnexpectedExpectedNode { oper: Some(Indexed { idx: 2, node: MyNode::Mul }), found: MyNode::Add });
+    assert_eq!(error.to_string(), "expected a node for Mul (at index 2), found Add");
+
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Tuple(0), &mut asa).unwrap();
+
+    // `Add Add 1 Mul 2 3 Tuple`
+    let error = parse::indexed(&mut asa, parse::separator).unwrap_err();
+    assert_eq!(error, IndexedError::EmptySlot(Indexed { idx: 6, node: MyNode::Tuple(0) }));
+    let error = parse::indexed(&mut asa, parse::ensure_completed).unwrap_err();
+    assert_eq!(error, IndexedError::ExpectedClose(Indexed { idx: 6, node: MyNode::Tuple(0) }));
+}
+
+#[test]
+fn map_node() {
+    let mut asa = asa();
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+
+    let error = parse::indexed(&mut asa, parse::ensure_completed).unwrap_err();
+    let error = error.map_node(|node| format!("{node:?}"));
+    assert_eq!(error, IndexedError::ExpectedNode(Indexed { idx: 0, node: "Add".to_string() }));
+}
+
+#[test]
+fn std_error() {
+    fn parse(nodes: Vec<MyNode>) -> Result<VectorASA<MyNode>, Box<dyn std::error::Error>> {
+        let mut asa = asa();
+        parse::indexed(&mut asa, |asa| parse::nodes(nodes, asa))?;
+        Ok(asa)
+    }
+
+    assert!(parse(vec![MyNode::Number(1)]).is_ok());
+    assert_eq!(parse(vec![MyNode::Number(1), MyNode::Number(2)]).unwrap_err().to_string(), "unexpected node Number(2) after a complete expression");
+}
+
+#[test]
+fn unindexed() {
+    // errors made owned without the ASA share the same type, just without the indexes
+    let mut asa = asa();
+    parse::nodes([MyNode::Number(1), MyNode::Add, MyNode::Tuple(0)], &mut asa).unwrap_err();
+    let error = parse::separator(&mut asa).unwrap_err().into_owned();
+    assert_eq!(error, OwnedError::EmptySlot(Indexed { idx: (), node: MyNode::Tuple(0) }));
+    assert_eq!(error.to_string(), "empty slot in Tuple(0)");
+
+    let mut empty = self::asa();
+    let error: Box<dyn std::error::Error> = Box::new(parse::ensure_completed(&mut empty).unwrap_err().into_owned());
+    assert_eq!(error.to_string(), "expected an expression, found nothing");
+}
@@ -0,0 +1,103 @@
+use ketchup::{asa::{VectorASA, ASA}, error::Error, node::Node, parse};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[binary(prec = 0)]
+    Add,
+    #[binary(prec = 1)]
+    Mul,
+    #[unary(prec = 2)]
+    Neg,
+}
+
+/// An owned error, like the ones of a real front-end
+#[derive(Debug, PartialEq, Eq)]
+enum MyError {
+    Lexer(char),
+    Unexpected(MyNode),
+    Expected(Option<MyNode>),
+    Other,
+}
+
+impl From<Error<'_, MyNode>> for MyError {
+    fn from(error: Error<MyNode>) -> Self {
+        match error {
+            Error::UnexpectedNode(node) => MyError::Unexpected(node),
+            Error::ExpectedNode(oper) => MyError::Expected(oper.cloned()),
+            _ => MyError::Other,
+        }
+    }
+}
+
+/// A very small lexer
+fn lex(src: &str) -> impl Iterator<Item = Result<MyNode, MyError>> + '_ {
+    src.chars().filter(|c| !c.is_whitespace()).map(|c| match c {
+        '0'..='9' => Ok(MyNode::Number(c.to_digit(10).unwrap() as i32)),
+        '+' => Ok(MyNode::Add),
+        '*' => Ok(MyNode::Mul),
+        '-' => Ok(MyNode::Neg),
+        _ => Err(MyError::Lexer(c)),
+    })
+}
+
+#[test]
+fn nodes() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::nodes([MyNode::Number(1), MyNode::Add, MyNode::Number(2), MyNode::Mul, MyNode::Number(3)], &mut asa).unwrap();
+
+    assert_eq!(asa.vector[..], [MyNode::Add, MyNode::Number(1), MyNode::Mul, MyNode::Number(2), MyNode::Number(3)]);
+}
+
+#[test]
+fn nodes_errors() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    let Err(Error::ExpectedNode(Some(MyNode::Mul))) = parse::nodes([MyNode::Number(1), MyNode::Mul], &mut asa)
+    else {
+        panic!("assert failed");
+    };
+
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    let Err(Error::UnexpectedExpectedNode { oper: Some(MyNode::Add), found: MyNode::Mul }) = parse::nodes([MyNode::Number(1), MyNode::Add, MyNode::Mul], &mut asa)
+    else {
+        panic!("assert failed");
+    };
+
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    let Err(Error::ExpectedNode(None)) = parse::nodes([], &mut asa)
+    else {
+        panic!("assert failed");
+    };
+}
+
+#[test]
+fn collect() {
+    let asa: VectorASA<MyNode> = parse::collect(lex("-1 * 2 + 3")).unwrap();
+    assert_eq!(asa.vector[..], [MyNode::Add, MyNode::Mul, MyNode::Neg, MyNode::Number(1), MyNode::Number(2), MyNode::Number(3)]);
+
+    assert_eq!(parse::collect::<VectorASA<MyNode>, _>(lex("1 + x")).unwrap_err(), MyError::Lexer('x'));
+    assert_eq!(parse::collect::<VectorASA<MyNode>, _>(lex("1 2")).unwrap_err(), MyError::Unexpected(MyNode::Number(2)));
+    assert_eq!(parse::collect::<VectorASA<MyNode>, _>(lex("1 + -")).unwrap_err(), MyError::Expected(Some(MyNode::Neg)));
+}
+
+#[test]
+fn fold() {
+    let mut asa = lex("1 * 2 + 3 * 4")
+        .map(Result::unwrap)
+        .try_fold(VectorASA::new(MyNode::MAX_PRECEDENCE), parse::step::<_, MyError>)
+        .unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+
+    assert_eq!(asa.vector[..], [
+        MyNode::Add,
+        MyNode::Mul, MyNode::Number(1), MyNode::Number(2),
+        MyNode::Mul, MyNode::Number(3), MyNode::Number(4),
+    ]);
+
+    let error = [MyNode::Number(1), MyNode::Number(2)]
+        .into_iter()
+        .try_fold(VectorASA::new(MyNode::MAX_PRECEDENCE), parse::step::<_, MyError>)
+        .unwrap_err();
+    assert_eq!(error, MyError::Unexpected(MyNode::Number(2)));
+}
@@ -0,0 +1,188 @@
+use ketchup::{asa::{VectorASA, ASA}, error::Error, node::{Mixfix, Node, NodeKind}, parse};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[mixfix(prec = 0, leading = false, holes = 2)]
+    If,
+    #[mixfix(prec = 1, assoc = right)]
+    Ternary,
+    #[binary(prec = 2)]
+    Add,
+    #[binary(prec = 3)]
+    Mul,
+    #[unary(prec = 4)]
+    Neg,
+}
+
+#[test]
+fn mixfix_kind() {
+    assert_eq!(MyNode::Ternary.get_kind(), NodeKind::Mixfix(Mixfix::TERNARY));
+    assert_eq!(MyNode::If.get_kind(), NodeKind::Mixfix(Mixfix { leading: false, holes: 2 }));
+}
+
+#[test]
+fn ternary() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // 1 + 2 ? 3 * 4 : 5 + 6
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    assert_eq!(parse::current_hole(&mut asa), Some((&MyNode::Ternary, 0)));
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::node(MyNode::Mul, &mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    assert_eq!(parse::current_hole(&mut asa), None);
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Number(6), &mut asa).unwrap();
+
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.vector[..], [
+        MyNode::Ternary,
+        MyNode::Add, MyNode::Number(1), MyNode::Number(2),
+        MyNode::Mul, MyNode::Number(3), MyNode::Number(4),
+        MyNode::Add, MyNode::Number(5), MyNode::Number(6),
+    ]);
+}
+
+#[test]
+fn ternary_low_precedence_in_hole() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // 1 * 2 ? 3 + -4 : 5
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Mul, &mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Neg, &mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.vector[..], [
+        MyNode::Ternary,
+        MyNode::Mul, MyNode::Number(1), MyNode::Number(2),
+        MyNode::Add, MyNode::Number(3), MyNode::Neg, MyNode::Number(4),
+        MyNode::Number(5),
+    ]);
+}
+
+#[test]
+fn ternary_right_associative() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // 1 ? 2 : 3 ? 4 ? 5 : 6 : 7
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(6), &mut asa).unwrap();
+    assert_eq!(parse::current_hole(&mut asa), Some((&MyNode::Ternary, 0)));
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(7), &mut asa).unwrap();
+
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.vector[..], [
+        MyNode::Ternary, MyNode::Number(1), MyNode::Number(2),
+        MyNode::Ternary, MyNode::Number(3),
+            MyNode::Ternary, MyNode::Number(4), MyNode::Number(5), MyNode::Number(6),
+            MyNode::Number(7),
+    ]);
+}
+
+#[test]
+fn if_then_else() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // if 1 then 2 else 3 + 4
+    parse::node(MyNode::If, &mut asa).unwrap();
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    assert_eq!(parse::current_hole(&mut asa), Some((&MyNode::If, 1)));
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.vector[..], [
+        MyNode::If, MyNode::Number(1), MyNode::Number(2), MyNode::Add, MyNode::Number(3), MyNode::Number(4),
+    ]);
+}
+
+#[test]
+fn nested_if_then_else() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // 1 * if 2 then 3 else 4 + 5
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Mul, &mut asa).unwrap();
+    parse::node(MyNode::If, &mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+
+    // the `Add` is in the else branch, even though the `Mul` before the `If` binds tighter than it
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.vector[..], [
+        MyNode::Mul, MyNode::Number(1),
+            MyNode::If, MyNode::Number(2), MyNode::Number(3), MyNode::Add, MyNode::Number(4), MyNode::Number(5),
+    ]);
+}
+
+#[test]
+fn missing_separator() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // 1 ? 2
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+
+    let Err(Error::ExpectedSeparator(MyNode::Ternary)) = parse::ensure_completed(&mut asa)
+    else {
+        panic!("assert failed");
+    };
+}
+
+#[test]
+fn hole_errors() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // : 1 ? : 2 + :
+    let Err(Error::UnexpectedSeparator) = parse::separator(&mut asa)
+    else {
+        panic!("assert failed");
+    };
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    let Err(Error::ExpectedNode(Some(MyNode::Ternary))) = parse::separator(&mut asa)
+    else {
+        panic!("assert failed");
+    };
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    let Err(Error::ExpectedNode(Some(MyNode::Add))) = parse::separator(&mut asa)
+    else {
+        panic!("assert failed");
+    };
+}
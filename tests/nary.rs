@@ -0,0 +1,131 @@
+use ketchup::{asa::{VectorASA, ASA}, error::Error, node::{Nary, Node, NodeKind}, parse};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[nary]
+    Tuple(usize),
+    #[nary(allow_trailing = true)]
+    List { len: usize },
+    #[binary(prec = 0)]
+    Add,
+    #[binary(prec = 1)]
+    Mul,
+}
+
+#[test]
+fn nary_kind() {
+    assert_eq!(MyNode::Tuple(3).get_kind(), NodeKind::Nary(Nary { arity: 3, allow_trailing: false }));
+    assert_eq!(MyNode::List { len: 0 }.get_kind(), NodeKind::Nary(Nary { arity: 0, allow_trailing: true }));
+}
+
+#[test]
+fn nary() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // (1 + 2, 3 * 4, 5)
+    parse::node(MyNode::Tuple(0), &mut asa).unwrap();
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::node(MyNode::Mul, &mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    assert_eq!(parse::current_hole(&mut asa), Some((&MyNode::Tuple(0), 2)));
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.vector[..], [
+        MyNode::Tuple(3),
+        MyNode::Add, MyNode::Number(1), MyNode::Number(2),
+        MyNode::Mul, MyNode::Number(3), MyNode::Number(4),
+        MyNode::Number(5),
+    ]);
+}
+
+#[test]
+fn nary_as_operand() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // 1 + [2 + 3, [],] * 4
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::List { len: 0 }, &mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::List { len: 0 }, &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::node(MyNode::Mul, &mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.vector[..], [
+        MyNode::Add,
+        MyNode::Number(1),
+        MyNode::Mul,
+            MyNode::List { len: 2 },
+                MyNode::Add, MyNode::Number(2), MyNode::Number(3),
+                MyNode::List { len: 0 },
+            MyNode::Number(4),
+    ]);
+}
+
+#[test]
+fn nary_errors() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // ] (1,, 2,)
+    let Err(Error::UnexpectedClose) = parse::close(&mut asa)
+    else {
+        panic!("assert failed");
+    };
+    parse::node(MyNode::Tuple(0), &mut asa).unwrap();
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    let Err(Error::EmptySlot(MyNode::Tuple(0))) = parse::separator(&mut asa)
+    else {
+        panic!("assert failed");
+    };
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    let Err(Error::TrailingSeparator(MyNode::Tuple(0))) = parse::close(&mut asa)
+    else {
+        panic!("assert failed");
+    };
+}
+
+#[test]
+fn unclosed_nary() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // [1 +
+    parse::node(MyNode::List { len: 0 }, &mut asa).unwrap();
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    let Err(Error::ExpectedNode(Some(MyNode::Add))) = parse::close(&mut asa)
+    else {
+        panic!("assert failed");
+    };
+
+    // [1 + 2
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    let Err(Error::ExpectedClose(MyNode::List { .. })) = parse::ensure_completed(&mut asa)
+    else {
+        panic!("assert failed");
+    };
+
+    // [1 + 2,
+    parse::separator(&mut asa).unwrap();
+    let Err(Error::ExpectedClose(MyNode::List { .. })) = parse::ensure_completed(&mut asa)
+    else {
+        panic!("assert failed");
+    };
+}
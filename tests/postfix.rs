@@ -0,0 +1,163 @@
+use ketchup::{asa::{VectorASA, ASA}, error::Error, node::{Nary, Node, NodeKind}, parse, tree::Tree, view::View, visit::{self, Visitor}};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Ident(char),
+    #[nary]
+    Tuple(usize),
+    #[binary(prec = 0)]
+    Add,
+    #[binary(prec = 1)]
+    Mul,
+    #[unary(prec = 2)]
+    Neg,
+    #[postfix(prec = 3)]
+    Call(usize),
+    #[postfix(prec = 3, allow_trailing = true)]
+    Index { len: usize },
+}
+
+use MyNode::*;
+
+/// A token of the tests' 'language', where postfix nodes are opened by their node and closed by a separate token
+#[derive(Debug, Clone)]
+enum Token {
+    Node(MyNode),
+    Separator,
+    Close,
+}
+
+fn n(node: MyNode) -> Token {
+    Token::Node(node)
+}
+
+const S: Token = Token::Separator;
+const C: Token = Token::Close;
+
+fn parse_tokens(tokens: impl IntoIterator<Item = Token>) -> Result<VectorASA<MyNode>, String> {
+    let mut asa = VectorASA::new(MyNode::MAX_PRECEDENCE);
+    for token in tokens {
+        match token {
+            Token::Node(node) => parse::node(node, &mut asa),
+            Token::Separator => parse::separator(&mut asa),
+            Token::Close => parse::close(&mut asa),
+        }.map_err(|error| format!("{error:?}"))?;
+    }
+    parse::ensure_completed(&mut asa).map_err(|error| format!("{error:?}"))?;
+    Ok(asa)
+}
+
+/// Renders the ASA back into source code
+struct Source;
+
+impl Visitor<MyNode> for Source {
+    type Output = String;
+
+    fn visit_operand(&mut self, node: &MyNode) -> String {
+        let Ident(ident) = node else { unreachable!() };
+        ident.to_string()
+    }
+
+    fn visit_unary(&mut self, _node: &MyNode, child: String) -> String {
+        format!("(-{child})")
+    }
+
+    fn visit_binary(&mut self, node: &MyNode, lhs: String, rhs: String) -> String {
+        format!("({lhs} {} {rhs})", if *node == Add { '+' } else { '*' })
+    }
+
+    fn visit_mixfix(&mut self, _node: &MyNode, _params: Vec<String>) -> String {
+        unreachable!()
+    }
+
+    fn visit_nary(&mut self, _node: &MyNode, params: Vec<String>) -> String {
+        format!("({})", params.join(", "))
+    }
+
+    fn visit_postfix(&mut self, node: &MyNode, operand: String, args: Vec<String>) -> String {
+        match node {
+            Call(_) => format!("{operand}({})", args.join(", ")),
+            _ => format!("{operand}[{}]", args.join(", ")),
+        }
+    }
+}
+
+#[test]
+fn postfix_kind() {
+    assert_eq!(Call(2).get_kind(), NodeKind::Postfix(Nary { arity: 2, allow_trailing: false }));
+    assert_eq!(Index { len: 0 }.get_kind(), NodeKind::Postfix(Nary { arity: 0, allow_trailing: true }));
+    assert_eq!(Call(0).get_precedence(), 3);
+    assert_eq!(MyNode::MAX_PRECEDENCE, 3);
+}
+
+#[test]
+fn postfix() {
+    // a + f(b, c * d)[e] * g
+    let asa = parse_tokens([
+        n(Ident('a')), n(Add), n(Ident('f')), n(Call(0)), n(Ident('b')), S, n(Ident('c')), n(Mul), n(Ident('d')), C,
+        n(Index { len: 0 }), n(Ident('e')), C, n(Mul), n(Ident('g')),
+    ]).unwrap();
+    assert_eq!(asa.vector, [
+        Add, Ident('a'),
+        Mul,
+        Index { len: 1 }, Call(2), Ident('f'), Ident('b'), Mul, Ident('c'), Ident('d'), Ident('e'),
+        Ident('g'),
+    ]);
+    assert_eq!(visit::walk(&asa, &mut Source), "(a + (f(b, (c * d))[e] * g))");
+
+    // the arguments of a postfix node are it's children after it's operand
+    let NodeKind::Postfix(Nary { arity, .. }) = asa.kind(4) else { panic!("expected a postfix node") };
+    assert_eq!(arity, 2);
+    let args = asa.children(4).skip(1).map(|idx| asa.subtree_range(idx)).collect::<Vec<_>>();
+    assert_eq!(args, [6..7, 7..10]);
+}
+
+#[test]
+fn postfix_arguments() {
+    // -f()(a)[b, c,] + (f, g(h))
+    let asa = parse_tokens([
+        n(Neg), n(Ident('f')), n(Call(0)), C, n(Call(0)), n(Ident('a')), C, n(Index { len: 0 }), n(Ident('b')), S, n(Ident('c')), S, C,
+        n(Add), n(Tuple(0)), n(Ident('f')), S, n(Ident('g')), n(Call(0)), n(Ident('h')), C, C,
+    ]).unwrap();
+    assert_eq!(visit::walk(&asa, &mut Source), "((-f()(a)[b, c]) + (f, g(h)))");
+
+    // only postfix nodes that allow trailing separators can have them, and their slots can't be empty
+    assert_eq!(parse_tokens([n(Ident('f')), n(Call(0)), n(Ident('a')), S, C]).unwrap_err(), "TrailingSeparator(Call(0))");
+    assert_eq!(parse_tokens([n(Ident('f')), n(Call(0)), n(Ident('a')), S, S]).unwrap_err(), "EmptySlot(Call(0))");
+    assert_eq!(parse_tokens([n(Ident('f')), n(Call(0)), n(Ident('a'))]).unwrap_err(), "ExpectedClose(Call(0))");
+
+    // postfix nodes need an operand before them
+    let mut asa = VectorASA::new(MyNode::MAX_PRECEDENCE);
+    parse::node(Ident('a'), &mut asa).unwrap();
+    parse::node(Add, &mut asa).unwrap();
+    assert!(matches!(parse::node(Call(0), &mut asa), Err(Error::UnexpectedExpectedNode { oper: Some(Add), found: Call(0) })));
+}
+
+#[test]
+fn postfix_tree() {
+    // f(a, b)
+    let tokens = [n(Ident('f')), n(Call(0)), n(Ident('a')), S, n(Ident('b')), C];
+    let asa = parse_tokens(tokens.clone()).unwrap();
+    let tree = Tree::from(&asa);
+    assert_eq!(tree, Tree::Postfix(Call(2), Box::new(Tree::Operand(Ident('f'))), vec![Tree::Operand(Ident('a')), Tree::Operand(Ident('b'))]));
+
+    // the flattened tree can still be parsed onto like the ASA it came from, without nodes being inserted into it's arguments
+    let mut flattened: VectorASA<MyNode> = tree.into_asa();
+    let mut asa = asa;
+    for asa in [&mut asa, &mut flattened] {
+        parse::node(Mul, asa).unwrap();
+        parse::node(Ident('c'), asa).unwrap();
+        parse::node(Call(0), asa).unwrap();
+        parse::close(asa).unwrap();
+    }
+    assert_eq!(flattened.vector, asa.vector);
+    assert_eq!(visit::walk(&flattened, &mut Source), "(f(a, b) * c())");
+
+    // (a, b) * c
+    let tuple = parse_tokens([n(Tuple(0)), n(Ident('a')), S, n(Ident('b')), C]).unwrap();
+    let mut flattened: VectorASA<MyNode> = Tree::from(&tuple).into_asa();
+    parse::node(Mul, &mut flattened).unwrap();
+    parse::node(Ident('c'), &mut flattened).unwrap();
+    assert_eq!(visit::walk(&flattened, &mut Source), "((a, b) * c)");
+}
@@ -0,0 +1,119 @@
+use ketchup::{asa::{ArrayASA, VectorASA, ASA}, error::{Indexed, IndexedError}, node::Node, parse::{self, Recover, Recovering}};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[operand]
+    Error,
+    #[nary]
+    Tuple(usize),
+    #[mixfix(prec = 0, assoc = right)]
+    Ternary,
+    #[binary(prec = 1)]
+    Add,
+    #[binary(prec = 2)]
+    Mul,
+    #[binary(prec = 3)]
+    Juxt,
+}
+
+fn empty() -> VectorASA<MyNode> {
+    VectorASA::new(MyNode::MAX_PRECEDENCE)
+}
+
+#[test]
+fn missing_and_surplus_operands() {
+    // `1 + * 2 3`
+    let mut asa = empty();
+    let errors = parse::nodes_recovering([MyNode::Number(1), MyNode::Add, MyNode::Mul, MyNode::Number(2), MyNode::Number(3)], &mut asa, || MyNode::Error);
+
+    assert_eq!(errors, [
+        IndexedError::UnexpectedExpectedNode { oper: Some(Indexed { idx: 0, node: MyNode::Add }), found: MyNode::Mul },
+        IndexedError::UnexpectedNode(MyNode::Number(3)),
+    ]);
+    assert!(*asa.is_complete());
+    assert_eq!(asa.vector, [MyNode::Add, MyNode::Number(1), MyNode::Mul, MyNode::Error, MyNode::Number(2)]);
+}
+
+#[test]
+fn incomplete_and_empty() {
+    // `1 +`
+    let mut asa = empty();
+    let errors = parse::nodes_recovering([MyNode::Number(1), MyNode::Add], &mut asa, || MyNode::Error);
+    assert_eq!(errors, [IndexedError::ExpectedNode(Indexed { idx: 0, node: MyNode::Add })]);
+    assert_eq!(asa.vector, [MyNode::Add, MyNode::Number(1), MyNode::Error]);
+
+    // ``
+    let mut asa = empty();
+    let errors = parse::nodes_recovering([], &mut asa, || MyNode::Error);
+    assert_eq!(errors, [IndexedError::EmptyExpression]);
+    assert_eq!(asa.vector, [MyNode::Error]);
+}
+
+#[test]
+fn join_surplus() {
+    struct Juxtapose;
+    impl Recover<MyNode> for Juxtapose {
+        fn placeholder(&mut self) -> MyNode {
+            MyNode::Error
+        }
+
+        fn join(&mut self, _surplus: &MyNode) -> Option<MyNode> {
+            Some(MyNode::Juxt)
+        }
+    }
+
+    // `1 + 2 3`
+    let mut asa = empty();
+    let errors = parse::nodes_recovering([MyNode::Number(1), MyNode::Add, MyNode::Number(2), MyNode::Number(3)], &mut asa, Juxtapose);
+    assert_eq!(errors, [IndexedError::UnexpectedNode(MyNode::Number(3))]);
+    assert_eq!(asa.vector, [MyNode::Add, MyNode::Number(1), MyNode::Juxt, MyNode::Number(2), MyNode::Number(3)]);
+}
+
+#[test]
+fn scopes() {
+    // `) (1,, 2 ? 3` (with the tuple and the ternary left open)
+    let mut asa = empty();
+    let mut recovering = Recovering::new(&mut asa, || MyNode::Error);
+    recovering.close();
+    recovering.nodes([MyNode::Tuple(0), MyNode::Number(1)]);
+    recovering.separator();
+    recovering.separator();
+    recovering.nodes([MyNode::Number(2), MyNode::Ternary, MyNode::Number(3)]);
+    let errors = recovering.finish();
+
+    assert_eq!(errors, [
+        IndexedError::UnexpectedClose,
+        IndexedError::EmptySlot(Indexed { idx: 0, node: MyNode::Tuple(0) }),
+        IndexedError::ExpectedSeparator(Indexed { idx: 3, node: MyNode::Ternary }),
+        IndexedError::ExpectedNode(Indexed { idx: 3, node: MyNode::Ternary }),
+        IndexedError::ExpectedClose(Indexed { idx: 0, node: MyNode::Tuple(0) }),
+    ]);
+    assert!(*asa.is_complete());
+    assert_eq!(asa.vector, [
+        MyNode::Tuple(3),
+            MyNode::Number(1),
+            MyNode::Error,
+            MyNode::Ternary, MyNode::Number(2), MyNode::Number(3), MyNode::Error,
+    ]);
+}
+
+#[test]
+fn full_asa() {
+    // `(1,` in an ASA with no room left for a placeholder, so the tuple can never be closed
+    let mut asa = ArrayASA::<MyNode, 2, { MyNode::MAX_PRECEDENCE + 1 }, 1>::new(MyNode::MAX_PRECEDENCE);
+    let mut recovering = Recovering::new(&mut asa, || MyNode::Error);
+    recovering.nodes([MyNode::Tuple(0), MyNode::Number(1)]);
+    recovering.separator();
+    recovering.node(MyNode::Number(2));
+    let errors = recovering.finish();
+
+    assert_eq!(errors, [
+        IndexedError::CapacityExceeded(MyNode::Number(2)),
+        IndexedError::TrailingSeparator(Indexed { idx: 0, node: MyNode::Tuple(0) }),
+        IndexedError::ExpectedClose(Indexed { idx: 0, node: MyNode::Tuple(0) }),
+    ]);
+    assert!(!*asa.is_complete());
+    assert_eq!(asa.get_len(), 2);
+}
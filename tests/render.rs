@@ -0,0 +1,103 @@
+use std::fmt;
+use ketchup::{asa::{VectorASA, ASA}, node::Node, parse, render, view::View};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[nary]
+    Tuple(usize),
+    #[mixfix(prec = 0, assoc = right)]
+    Ternary,
+    #[binary(prec = 1)]
+    Add,
+    #[binary(prec = 2)]
+    Mul,
+    #[unary(prec = 3)]
+    Neg,
+}
+
+impl fmt::Display for MyNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MyNode::Number(num) => write!(f, "{num}"),
+            MyNode::Tuple(_) => write!(f, "tuple"),
+            MyNode::Ternary => write!(f, "?:"),
+            MyNode::Add => write!(f, "+"),
+            MyNode::Mul => write!(f, "*"),
+            MyNode::Neg => write!(f, "-"),
+        }
+    }
+}
+
+/// Parses `12 + 4 * 8`
+fn simple() -> VectorASA<MyNode> {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::nodes([MyNode::Number(12), MyNode::Add, MyNode::Number(4), MyNode::Mul, MyNode::Number(8)], &mut asa).unwrap();
+    asa
+}
+
+/// Parses `-1 ? (2, 3 + 4, ()) : 5`
+fn nested() -> VectorASA<MyNode> {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::node(MyNode::Neg, &mut asa).unwrap();
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Tuple(0), &mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Tuple(0), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+    asa
+}
+
+#[test]
+fn sexpr() {
+    assert_eq!(render::sexpr(&simple()).to_string(), "(+ 12 (* 4 8))");
+    assert_eq!(format!("{:?}", render::sexpr(&simple())), "(Add Number(12) (Mul Number(4) Number(8)))");
+    assert_eq!(render::sexpr(&nested()).to_string(), "(?: (- 1) (tuple 2 (+ 3 4) (tuple)) 5)");
+    assert_eq!(render::sexpr(&nested().subtree(3)).to_string(), "(tuple 2 (+ 3 4) (tuple))");
+}
+
+#[test]
+fn tree() {
+    assert_eq!(render::tree(&simple()).to_string(), "\
++
+├── 12
+└── *
+    ├── 4
+    └── 8");
+
+    assert_eq!(render::tree(&nested()).to_string(), "\
+?:
+├── -
+│   └── 1
+├── tuple
+│   ├── 2
+│   ├── +
+│   │   ├── 3
+│   │   └── 4
+│   └── tuple
+└── 5");
+}
+
+#[test]
+fn pretty_debug() {
+    let asa = simple();
+    assert_eq!(format!("{asa:#?}"), format!("{:?}", render::tree(&asa)));
+
+    // incomplete ASAs are still printed as-is
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    assert!(format!("{asa:#?}").starts_with("VectorASA {"));
+    assert!(format!("{asa:?}").starts_with("VectorASA {"));
+}
@@ -0,0 +1,33 @@
+use ketchup::{asa::{RopeASA, VectorASA, ASA}, parse};
+
+mod common;
+
+use common::{chain, MyNode};
+
+#[test]
+fn matches_vector() {
+    // enough nodes for the rope to be more than one level deep
+    let vector = chain::<VectorASA<MyNode>>(5000);
+    let mut rope = chain::<RopeASA<MyNode>>(5000);
+
+    assert_eq!(rope.get_len(), vector.vector.len());
+    assert!((0..rope.get_len()).all(|idx| rope.get_node(idx) == &vector.vector[idx]));
+
+    *rope.get_node_mut(6000) = MyNode::Number(-1);
+    assert_eq!(rope.get_node(6000), &MyNode::Number(-1));
+    assert_eq!(rope.into_vec().len(), vector.vector.len());
+}
+
+#[test]
+fn into_vector_asa() {
+    let rope = chain::<RopeASA<MyNode>>(100);
+    let mut asa = VectorASA::from(rope);
+    assert_eq!(asa.vector, chain::<VectorASA<MyNode>>(100).vector);
+
+    // the parsing state carries over
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Number(0), &mut asa).unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.vector[0], MyNode::Add);
+    assert_eq!(asa.vector.last(), Some(&MyNode::Number(0)));
+}
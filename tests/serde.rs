@@ -0,0 +1,130 @@
+#![cfg(feature = "serde")]
+
+use ketchup::{asa::{VectorASA, ASA}, error::{Error, Indexed, OwnedError}, node::{Alignment, Node, NodeKind}, parse};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node, Serialize, Deserialize)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[nary]
+    Tuple(usize),
+    #[binary(prec = 0)]
+    Add,
+    #[binary(prec = 1)]
+    Mul,
+}
+
+#[test]
+fn round_trip() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::nodes([MyNode::Number(1), MyNode::Add, MyNode::Number(2), MyNode::Mul, MyNode::Number(3)], &mut asa).unwrap();
+
+    let json = serde_json::to_string(&asa).unwrap();
+    let de: VectorASA<MyNode> = serde_json::from_str(&json).unwrap();
+    assert_eq!(de.vector, asa.vector);
+    assert_eq!(format!("{de:?}"), format!("{asa:?}"));
+}
+
+#[test]
+fn resume_partial() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // (1 * 2, 3 +
+    parse::node(MyNode::Tuple(0), &mut asa).unwrap();
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Mul, &mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+
+    let json = serde_json::to_string(&asa).unwrap();
+    let mut asa: VectorASA<MyNode> = serde_json::from_str(&json).unwrap();
+    assert!(!*asa.is_complete());
+
+    // 4 * 5)
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::node(MyNode::Mul, &mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+
+    assert_eq!(asa.vector[..], [
+        MyNode::Tuple(2),
+        MyNode::Mul, MyNode::Number(1), MyNode::Number(2),
+        MyNode::Add, MyNode::Number(3), MyNode::Mul, MyNode::Number(4), MyNode::Number(5),
+    ]);
+}
+
+#[test]
+fn resume_open_group() {
+    // 1 + (
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::open_group(&mut asa).unwrap();
+
+    // the group starts at the index that the next node will be pushed to
+    let json = serde_json::to_string(&asa).unwrap();
+    let mut asa: VectorASA<MyNode> = serde_json::from_str(&json).unwrap();
+
+    // 2 + 3) * 4
+    for node in [MyNode::Number(2), MyNode::Add, MyNode::Number(3)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::close_group(&mut asa).unwrap();
+    parse::nodes([MyNode::Mul, MyNode::Number(4)], &mut asa).unwrap();
+
+    assert_eq!(asa.vector[..], [
+        MyNode::Add, MyNode::Number(1), MyNode::Mul, MyNode::Add, MyNode::Number(2), MyNode::Number(3), MyNode::Number(4),
+    ]);
+
+    // a group can also start an empty ASA
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::open_group(&mut asa).unwrap();
+    let json = serde_json::to_string(&asa).unwrap();
+    assert!(serde_json::from_str::<VectorASA<MyNode>>(&json).is_ok());
+
+    // but not any further than that
+    let mut json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    json["scopes"][0]["scope"]["node"] = serde_json::json!(1);
+    let error = serde_json::from_value::<VectorASA<MyNode>>(json).unwrap_err();
+    assert!(error.to_string().contains("index 1 is out of bounds of an ASA of length 0"));
+}
+
+#[test]
+fn invalid_jumptable() {
+    let asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    let mut json: serde_json::Value = serde_json::to_value(&asa).unwrap();
+
+    json["precedence_jumptable"] = serde_json::json!([null, null, null]);
+    let error = serde_json::from_value::<VectorASA<MyNode>>(json.clone()).unwrap_err();
+    assert!(error.to_string().contains("lookup-table has 3 entries, but the max precedence is 1"));
+
+    json["precedence_jumptable"] = serde_json::json!([null, 0]);
+    let error = serde_json::from_value::<VectorASA<MyNode>>(json).unwrap_err();
+    assert!(error.to_string().contains("index 0 is out of bounds of an ASA of length 0"));
+}
+
+#[test]
+fn node_kind() {
+    let kind = NodeKind::Unary(Alignment::Right);
+    let json = serde_json::to_string(&kind).unwrap();
+    assert_eq!(serde_json::from_str::<NodeKind>(&json).unwrap(), kind);
+}
+
+#[test]
+fn owned_error() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    let error = parse::nodes([MyNode::Number(1), MyNode::Mul], &mut asa).unwrap_err();
+    let Error::ExpectedNode(Some(MyNode::Mul)) = error else {
+        panic!("assert failed");
+    };
+
+    let owned = error.into_owned();
+    assert_eq!(owned, OwnedError::ExpectedNode(Indexed { idx: (), node: MyNode::Mul }));
+
+    let json = serde_json::to_string(&owned).unwrap();
+    assert_eq!(serde_json::from_str::<OwnedError<MyNode>>(&json).unwrap(), owned);
+}
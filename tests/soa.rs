@@ -0,0 +1,54 @@
+use ketchup::{asa::{Removable, SoaASA, VectorASA, ASA}, node::{Nary, NodeKind}, render};
+
+mod common;
+
+use common::{example, MyNode};
+
+#[test]
+fn matches_vector() {
+    let vector = example::<VectorASA<MyNode>>();
+    let soa = example::<SoaASA<MyNode>>();
+
+    assert_eq!(soa.get_len(), vector.vector.len());
+    assert!((0..soa.get_len()).all(|idx| soa.get_node(idx) == &vector.vector[idx]));
+    assert_eq!(format!("{:?}", render::sexpr(&soa)), format!("{:?}", render::sexpr(&vector)));
+    assert_eq!(VectorASA::from(soa).vector, vector.vector);
+}
+
+#[test]
+fn cached_kind_and_precedence() {
+    let mut soa = example::<SoaASA<MyNode>>();
+
+    assert_eq!(soa.get_kind(0), NodeKind::Binary);
+    assert_eq!(soa.get_precedence(0), 1);
+    assert_eq!(soa.get_precedence(2), 2);
+
+    // the precedence is read from the slot, not the payload
+    *soa.get_node_mut(0) = MyNode::Mul;
+    assert_eq!(soa.get_precedence(0), 1);
+    *soa.get_node_mut(0) = MyNode::Add;
+
+    // the arity of the closed n-ary node is cached alongside it
+    let NodeKind::Nary(Nary { arity, .. }) = soa.get_kind(6) else { panic!("expected an n-ary node") };
+    assert_eq!(arity, 2);
+    soa.set_arity(6, 3);
+    assert_eq!(soa.get_node(6), &MyNode::Tuple(3));
+    assert!(matches!(soa.get_kind(6), NodeKind::Nary(Nary { arity: 3, .. })));
+}
+
+#[test]
+fn remove() {
+    let mut vector = example::<VectorASA<MyNode>>();
+    let mut soa = example::<SoaASA<MyNode>>();
+
+    // removing from the middle and then from the end keeps the rest of the nodes in order
+    for idx in [3, 0, 8, 7] {
+        assert_eq!(soa.remove(idx), vector.remove(idx));
+        assert!((0..soa.get_len()).all(|idx| soa.get_node(idx) == &vector.vector[idx]));
+    }
+
+    // new nodes are still pushed after the holes left in the payloads
+    soa.push(MyNode::Number(7));
+    vector.push(MyNode::Number(7));
+    assert_eq!(VectorASA::from(soa).vector, vector.vector);
+}
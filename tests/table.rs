@@ -0,0 +1,149 @@
+use ketchup::{asa::{VectorASA, ASA}, compact::Operator, error::Error, node::{Alignment, Associativity, Nary, Node, NodeKind}, parse, render, table::{Dynamic, OperatorTable}, tree::Tree};
+
+/// Declares the operators of a small language from `infixl 6 <+>`-like declarations
+fn declarations(source: &str) -> OperatorTable<String> {
+    let mut table = OperatorTable::new();
+    for line in source.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let [fixity, precedence, symbol] = line.split_whitespace().collect::<Vec<_>>()[..] else { panic!("invalid declaration {line:?}") };
+        let precedence = precedence.parse().unwrap();
+        let operator = match fixity {
+            "infixl" => Operator::binary(precedence),
+            "infixr" => Operator::binary(precedence).assoc(Associativity::Right),
+            "prefix" => Operator::unary(Alignment::Left, precedence),
+            "postfix" => Operator::unary(Alignment::Right, precedence),
+            _ => panic!("unknown fixity {fixity:?}"),
+        };
+        table.declare(symbol.to_string(), operator);
+    }
+    table
+}
+
+/// Parses space-separated tokens, where every undeclared token is an operand
+fn parse(table: &OperatorTable<String>, source: &str) -> VectorASA<Dynamic<String>> {
+    let mut asa = table.asa();
+    for token in source.split_whitespace() {
+        let node = table.node(token, token.to_string()).unwrap_or_else(|| Dynamic::operand(token.to_string()));
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::ensure_completed(&mut asa).unwrap();
+    asa
+}
+
+fn sexpr(asa: &VectorASA<Dynamic<String>>) -> String {
+    format!("{}", render::sexpr(asa))
+}
+
+const DECLARATIONS: &str = "
+    infixl 6 <+>
+    infixl 7 <*>
+    infixr 5 ++
+    infixr 8 ^
+    prefix 9 ~
+    postfix 10 !
+";
+
+#[test]
+fn runtime_precedences() {
+    let table = declarations(DECLARATIONS);
+    assert_eq!(table.max_precedence(), 5);
+    assert_eq!(table.get("<+>"), Some(&Operator::binary(6)));
+    assert!(table.node("<->", ()).is_none());
+
+    let asa = parse(&table, "a <+> b <*> c <+> d");
+    assert_eq!(sexpr(&asa), "(<+> (<+> a (<*> b c)) d)");
+    assert_eq!(parse(&table, "a ++ b ++ c <+> d").vector.iter().map(|node| &node.payload[..]).collect::<Vec<_>>(), ["++", "a", "++", "b", "<+>", "c", "d"]);
+    assert_eq!(sexpr(&parse(&table, "~ a ^ b ^ c !")), "(^ (~ a) (^ b (! c)))");
+
+    // the lookup-table is sized from the table, not from `Node::MAX_PRECEDENCE`
+    let mut asa = parse(&table, "a");
+    assert_eq!(asa.lookuptable().len(), 6);
+}
+
+#[test]
+fn redeclare() {
+    let mut table = declarations(DECLARATIONS);
+
+    // infixl 9 <+> (which leaves precedence 6 unused)
+    assert_eq!(table.declare("<+>".to_string(), Operator::binary(9)), Some(Operator::binary(6)));
+    assert_eq!(table.max_precedence(), 4);
+    assert_eq!(sexpr(&parse(&table, "a <+> b <*> c")), "(<*> (<+> a b) c)");
+
+    // n-ary and postfix nodes keep their arity in the resolved operator
+    table.declare("[".to_string(), Operator::nary(false));
+    table.declare("(".to_string(), Operator::postfix(10, true));
+    let mut asa = table.asa::<VectorASA<_>>();
+    parse::node(Dynamic::operand("f"), &mut asa).unwrap();
+    parse::node(table.node("(", "call").unwrap(), &mut asa).unwrap();
+    parse::node(table.node("[", "list").unwrap(), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+    assert_eq!(asa.vector[0].operator().kind, NodeKind::Postfix(Nary { arity: 1, allow_trailing: true }));
+    assert_eq!(asa.vector[2].operator().kind, NodeKind::Nary(Nary { arity: 0, allow_trailing: false }));
+
+    // trees of dynamic nodes are flattened with the table's precedence
+    let tree = Tree::from(&asa);
+    let asa: VectorASA<_> = tree.clone().into_asa_with(table.max_precedence());
+    assert_eq!(Tree::from(&asa), tree);
+}
+
+#[test]
+fn sparse_precedences() {
+    // precedences spaced out by 10, like those copied from a spec, only take up as many lookup-table entries as there are distinct precedences
+    let table = declarations("
+        infixl 60 <+>
+        infixl 70 <*>
+        infixr 70 <.>
+        infixr 80 ^
+        prefix 1000 ~
+    ");
+    assert_eq!(table.max_precedence(), 3);
+    assert_eq!(table.get("^"), Some(&Operator::binary(80).assoc(Associativity::Right)));
+    assert_eq!(table.node("^", ()).unwrap().operator().precedence, 80);
+    assert_eq!(table.node("^", ()).unwrap().get_precedence(), 2);
+    assert_eq!(table.dense(75), 2);
+
+    let mut asa = parse(&table, "~ a <+> b <*> c ^ d");
+    assert_eq!(asa.lookuptable().len(), 4);
+    assert_eq!(sexpr(&asa), "(<+> (~ a) (<*> b (^ c d)))");
+
+    // a level between two others shifts the dense precedences above it
+    let mut table = table;
+    table.declare("<>".to_string(), Operator::binary(65));
+    assert_eq!(table.max_precedence(), 4);
+    assert_eq!(table.node("^", ()).unwrap().get_precedence(), 3);
+    assert_eq!(sexpr(&parse(&table, "a <+> b <> c <*> d")), "(<+> a (<> b (<*> c d)))");
+
+    // negative precedences (like Haskell's `infixr 0 $`, shifted below it) are remapped the same way
+    table.declare("$".to_string(), Operator::binary(-1).assoc(Associativity::Right));
+    assert_eq!(table.dense(-1), 0);
+    assert_eq!(table.node("<+>", ()).unwrap().get_precedence(), 1);
+    assert_eq!(sexpr(&parse(&table, "~ f $ g $ a <+> b")), "($ (~ f) ($ g (<+> a b)))");
+}
+
+/// An error from parsing, with the nodes it borrows dropped
+#[derive(Debug)]
+struct Invalid;
+
+impl<N: Node> From<Error<'_, N>> for Invalid {
+    fn from(_: Error<'_, N>) -> Self {
+        Invalid
+    }
+}
+
+#[test]
+fn collect_with() {
+    let table = declarations(DECLARATIONS);
+    let nodes = "a <*> b <+> c".split_whitespace().map(|token| Ok::<_, Invalid>(table.node(token, token).unwrap_or_else(|| Dynamic::operand(token))));
+    let mut asa: VectorASA<_> = parse::collect_with(table.max_precedence(), nodes).unwrap();
+    assert_eq!(asa.lookuptable().len(), 6);
+    assert_eq!(asa.vector.iter().map(|node| node.payload).collect::<Vec<_>>(), ["<+>", "<*>", "a", "b", "c"]);
+}
+
+#[test]
+#[should_panic = "only known at runtime"]
+fn static_max_precedence() {
+    // dynamic nodes don't have a maximum precedence at compile time, so creating an ASA with it panics instead of allocating `usize::MAX` entries
+    VectorASA::<Dynamic<&str>>::new(Dynamic::<&str>::MAX_PRECEDENCE);
+}
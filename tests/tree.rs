@@ -0,0 +1,152 @@
+use ketchup::{asa::{VectorASA, ASA}, node::Node, parse, tree::Tree};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[nary]
+    Tuple(usize),
+    #[mixfix(prec = 0, assoc = right)]
+    Ternary,
+    #[binary(prec = 1)]
+    Add,
+    #[binary(prec = 2)]
+    Mul,
+    #[unary(prec = 3)]
+    Neg,
+    #[unary(prec = 4, align = right)]
+    Call,
+}
+
+fn parse_nodes(nodes: impl IntoIterator<Item = MyNode>) -> VectorASA<MyNode> {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::nodes(nodes, &mut asa).unwrap();
+    asa
+}
+
+fn num(num: i32) -> Box<Tree<MyNode>> {
+    Box::new(Tree::Operand(MyNode::Number(num)))
+}
+
+#[test]
+fn from_asa() {
+    // -1 * 2 + 3
+    let asa = parse_nodes([MyNode::Neg, MyNode::Number(1), MyNode::Mul, MyNode::Number(2), MyNode::Add, MyNode::Number(3)]);
+    let tree = Tree::from(&asa);
+
+    assert_eq!(tree, Tree::Binary(
+        MyNode::Add,
+        Box::new(Tree::Binary(MyNode::Mul, Box::new(Tree::Unary(MyNode::Neg, num(1))), num(2))),
+        num(3),
+    ));
+    assert_eq!(*tree.node(), MyNode::Add);
+}
+
+#[test]
+fn round_trip() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // (1 ? 2 : 3, 4 * -5)
+    parse::node(MyNode::Tuple(0), &mut asa).unwrap();
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::node(MyNode::Mul, &mut asa).unwrap();
+    parse::node(MyNode::Neg, &mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+
+    let tree = Tree::from(&asa);
+    let Tree::Nary(MyNode::Tuple(2), ref params) = tree else {
+        panic!("assert failed");
+    };
+    assert_eq!(params[0], Tree::Mixfix(MyNode::Ternary, vec![*num(1), *num(2), *num(3)]));
+
+    let flattened = VectorASA::from(tree);
+    assert_eq!(flattened.vector, asa.vector);
+}
+
+#[test]
+fn into_asa_sets_arity() {
+    let tree = Tree::Nary(MyNode::Tuple(0), vec![*num(1), *num(2)]);
+    let asa: VectorASA<MyNode> = tree.into_asa();
+    assert_eq!(asa.vector[..], [MyNode::Tuple(2), MyNode::Number(1), MyNode::Number(2)]);
+}
+
+/// Converting into a tree and back should leave the ASA in the same state as the parser did, so that parsing can continue from where it left off
+#[test]
+fn continue_parsing() {
+    let prefixes: [&[MyNode]; 5] = [
+        &[MyNode::Number(1), MyNode::Mul, MyNode::Number(2)],
+        &[MyNode::Number(1), MyNode::Add, MyNode::Number(2)],
+        &[MyNode::Neg, MyNode::Number(1), MyNode::Add, MyNode::Number(2), MyNode::Mul, MyNode::Number(3)],
+        &[MyNode::Number(1), MyNode::Add, MyNode::Number(2), MyNode::Call],
+        &[MyNode::Number(1), MyNode::Mul, MyNode::Neg, MyNode::Number(2), MyNode::Call],
+    ];
+    let suffixes: [&[MyNode]; 4] = [
+        &[MyNode::Add, MyNode::Number(9)],
+        &[MyNode::Mul, MyNode::Number(9)],
+        &[MyNode::Call],
+        &[MyNode::Mul, MyNode::Number(8), MyNode::Add, MyNode::Number(9)],
+    ];
+
+    for prefix in prefixes {
+        for suffix in suffixes {
+            let expected = parse_nodes(prefix.iter().chain(suffix).cloned());
+
+            let mut asa: VectorASA<MyNode> = Tree::from(&parse_nodes(prefix.iter().cloned())).into_asa();
+            parse::nodes(suffix.iter().cloned(), &mut asa).unwrap();
+
+            assert_eq!(asa.vector, expected.vector, "{prefix:?} {suffix:?}");
+        }
+    }
+}
+
+/// Groups on the right-most path are closed off, so they have to stay closed off after converting into a tree and back
+#[test]
+fn continue_parsing_after_group() {
+    fn group(before: &[MyNode], inside: &[MyNode], asa: &mut VectorASA<MyNode>) {
+        for node in before {
+            parse::node(node.clone(), asa).unwrap();
+        }
+        parse::open_group(asa).unwrap();
+        for node in inside {
+            parse::node(node.clone(), asa).unwrap();
+        }
+        parse::close_group(asa).unwrap();
+    }
+
+    let prefixes: [fn(&mut VectorASA<MyNode>); 3] = [
+        // 2 * (3 + 4)
+        |asa| group(&[MyNode::Number(2), MyNode::Mul], &[MyNode::Number(3), MyNode::Add, MyNode::Number(4)], asa),
+        // 1 * -(2 + 3)
+        |asa| group(&[MyNode::Number(1), MyNode::Mul, MyNode::Neg], &[MyNode::Number(2), MyNode::Add, MyNode::Number(3)], asa),
+        // 1 + (2 + 3)
+        |asa| group(&[MyNode::Number(1), MyNode::Add], &[MyNode::Number(2), MyNode::Add, MyNode::Number(3)], asa),
+    ];
+    let suffixes: [&[MyNode]; 3] = [
+        &[MyNode::Add, MyNode::Number(5)],
+        &[MyNode::Mul, MyNode::Number(5)],
+        &[MyNode::Call],
+    ];
+
+    for prefix in prefixes {
+        for suffix in suffixes {
+            let mut expected = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+            prefix(&mut expected);
+            parse::nodes(suffix.iter().cloned(), &mut expected).unwrap();
+
+            let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+            prefix(&mut asa);
+            let mut asa: VectorASA<MyNode> = Tree::from(&asa).into_asa();
+            parse::nodes(suffix.iter().cloned(), &mut asa).unwrap();
+
+            assert_eq!(asa.vector, expected.vector, "{suffix:?}");
+        }
+    }
+}
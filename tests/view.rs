@@ -0,0 +1,151 @@
+use ketchup::{asa::{VectorASA, ASA}, node::Node, parse, view::{SkipView, View}};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[nary]
+    Tuple(usize),
+    #[mixfix(prec = 0, assoc = right)]
+    Ternary,
+    #[binary(prec = 1)]
+    Add,
+    #[binary(prec = 2)]
+    Mul,
+    #[unary(prec = 3)]
+    Neg,
+}
+
+/// Parses `-1 * 2 + (3, 4 ? 5 : 6)`
+fn example() -> VectorASA<MyNode> {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    for node in [MyNode::Neg, MyNode::Number(1), MyNode::Mul, MyNode::Number(2), MyNode::Add, MyNode::Tuple(0), MyNode::Number(3)] {
+        parse::node(node, &mut asa).unwrap();
+    }
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::node(MyNode::Ternary, &mut asa).unwrap();
+    parse::node(MyNode::Number(5), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(6), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+
+    assert_eq!(asa.vector[..], [
+        MyNode::Add,
+            MyNode::Mul, MyNode::Neg, MyNode::Number(1), MyNode::Number(2),
+            MyNode::Tuple(2),
+                MyNode::Number(3),
+                MyNode::Ternary, MyNode::Number(4), MyNode::Number(5), MyNode::Number(6),
+    ]);
+    asa
+}
+
+#[test]
+fn children() {
+    let asa = example();
+
+    assert_eq!(asa.children(0).collect::<Vec<_>>(), [1, 5]);
+    assert_eq!(asa.children(1).collect::<Vec<_>>(), [2, 4]);
+    assert_eq!(asa.children(5).collect::<Vec<_>>(), [6, 7]);
+    assert_eq!(asa.children(7).collect::<Vec<_>>(), [8, 9, 10]);
+    assert_eq!(asa.children(3).len(), 0);
+}
+
+#[test]
+fn parent_and_depth() {
+    let asa = example();
+
+    assert_eq!(asa.parent(0), None);
+    assert_eq!(asa.parent(1), Some(0));
+    assert_eq!(asa.parent(4), Some(1));
+    assert_eq!(asa.parent(5), Some(0));
+    assert_eq!(asa.parent(10), Some(7));
+    assert_eq!(asa.depth(0), 0);
+    assert_eq!(asa.depth(3), 3);
+    assert_eq!(asa.depth(9), 3);
+
+    // every node is the parent of it's children
+    for idx in 0..asa.len() {
+        assert!(asa.children(idx).all(|child| asa.parent(child) == Some(idx)));
+    }
+}
+
+#[test]
+fn parent_and_depth_deep() {
+    // ----...1 (deep enough for a quadratic walk up the tree to be too slow)
+    let depth = 100_000;
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::nodes(std::iter::repeat(MyNode::Neg).take(depth).chain([MyNode::Number(1), MyNode::Add, MyNode::Number(2)]), &mut asa).unwrap();
+
+    // `Add (Neg (Neg ... 1)) 2`
+    assert_eq!(asa.parent(depth + 1), Some(depth));
+    assert_eq!(asa.depth(depth + 1), depth + 1);
+    assert_eq!(asa.parent(depth + 2), Some(0));
+    assert_eq!(asa.depth(depth + 2), 1);
+}
+
+#[test]
+fn subtree_range() {
+    let asa = example();
+
+    assert_eq!(asa.subtree_range(0), 0..11);
+    assert_eq!(asa.subtree_range(1), 1..5);
+    assert_eq!(asa.subtree_range(2), 2..4);
+    assert_eq!(asa.subtree_range(5), 5..11);
+    assert_eq!(asa.subtree_range(8), 8..9);
+}
+
+#[test]
+fn iter_with_depth() {
+    let asa = example();
+
+    let depths = asa.iter_with_depth().map(|(idx, depth, _)| (idx, depth)).collect::<Vec<_>>();
+    assert_eq!(depths, [(0, 0), (1, 1), (2, 2), (3, 3), (4, 2), (5, 1), (6, 2), (7, 2), (8, 3), (9, 3), (10, 3)]);
+    assert!(asa.iter_with_depth().all(|(idx, depth, node)| asa.depth(idx) == depth && asa.node(idx) == node));
+}
+
+#[test]
+fn subtree_view() {
+    let asa = example();
+
+    let tuple = asa.subtree(5);
+    assert_eq!(tuple.range(), 5..11);
+    assert_eq!(*tuple.root(), MyNode::Tuple(2));
+    assert_eq!(tuple.len(), 6);
+    assert_eq!(tuple.children(0).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(tuple.parent(3), Some(2));
+
+    // views of views
+    let ternary = tuple.subtree(2);
+    assert_eq!(ternary.range(), 2..6);
+    assert_eq!(*ternary.root(), MyNode::Ternary);
+    assert_eq!(ternary.iter_with_depth().map(|(_, _, node)| node.clone()).collect::<Vec<_>>(), [
+        MyNode::Ternary, MyNode::Number(4), MyNode::Number(5), MyNode::Number(6),
+    ]);
+}
+
+#[test]
+#[should_panic]
+fn subtree_view_out_of_bounds() {
+    let asa = example();
+    asa.subtree(1).node(4); // should panic
+}
+
+#[test]
+fn skip_view() {
+    let asa = example();
+    let skip = SkipView::new(&asa);
+
+    assert_eq!((0..asa.len()).map(|idx| skip.subtree_len(idx)).collect::<Vec<_>>(), [11, 4, 2, 1, 1, 6, 1, 4, 1, 1, 1]);
+    for idx in 0..asa.len() {
+        assert_eq!(skip.subtree_range(idx), asa.subtree_range(idx));
+        assert_eq!(skip.parent(idx), asa.parent(idx));
+        assert!(skip.children(idx).eq(asa.children(idx)));
+    }
+
+    // subtree views skip using the table of the view they are of
+    let tuple = skip.subtree(5);
+    assert_eq!(tuple.subtree_range(2), 2..6);
+    assert_eq!(tuple.children(0).collect::<Vec<_>>(), [1, 2]);
+}
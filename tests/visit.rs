@@ -0,0 +1,222 @@
+use ketchup::{asa::{VectorASA, ASA}, node::Node, parse, visit::{self, TryVisitor, Visitor, VisitorMut}};
+
+#[derive(Debug, Clone, PartialEq, Eq, Node)]
+enum MyNode {
+    #[operand]
+    Number(i32),
+    #[nary]
+    Sum(usize),
+    #[mixfix(prec = 0, leading = false, holes = 2)]
+    If,
+    #[binary(prec = 1)]
+    Add,
+    #[binary(prec = 2)]
+    Div,
+    #[unary(prec = 3)]
+    Neg,
+}
+
+fn parse_nodes(nodes: impl IntoIterator<Item = MyNode>) -> VectorASA<MyNode> {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::nodes(nodes, &mut asa).unwrap();
+    asa
+}
+
+struct Evaluator;
+
+impl Visitor<MyNode> for Evaluator {
+    type Output = i32;
+
+    fn visit_operand(&mut self, node: &MyNode) -> i32 {
+        let MyNode::Number(num) = node else { unreachable!() };
+        *num
+    }
+
+    fn visit_unary(&mut self, _node: &MyNode, child: i32) -> i32 {
+        -child
+    }
+
+    fn visit_binary(&mut self, node: &MyNode, lhs: i32, rhs: i32) -> i32 {
+        match node {
+            MyNode::Add => lhs + rhs,
+            MyNode::Div => lhs / rhs,
+            _ => unreachable!(),
+        }
+    }
+
+    fn visit_mixfix(&mut self, _node: &MyNode, params: Vec<i32>) -> i32 {
+        if params[0] != 0 { params[1] } else { params[2] }
+    }
+
+    fn visit_nary(&mut self, _node: &MyNode, params: Vec<i32>) -> i32 {
+        params.into_iter().sum()
+    }
+
+    fn visit_postfix(&mut self, _node: &MyNode, _operand: i32, _args: Vec<i32>) -> i32 {
+        unreachable!()
+    }
+}
+
+/// Records the order that nodes are visited in
+struct Order(Vec<MyNode>);
+
+impl Visitor<MyNode> for Order {
+    type Output = ();
+
+    fn visit_operand(&mut self, node: &MyNode) { self.0.push(node.clone()) }
+    fn visit_unary(&mut self, node: &MyNode, _: ()) { self.0.push(node.clone()) }
+    fn visit_binary(&mut self, node: &MyNode, _: (), _: ()) { self.0.push(node.clone()) }
+    fn visit_mixfix(&mut self, node: &MyNode, _: Vec<()>) { self.0.push(node.clone()) }
+    fn visit_nary(&mut self, node: &MyNode, _: Vec<()>) { self.0.push(node.clone()) }
+    fn visit_postfix(&mut self, node: &MyNode, _: (), _: Vec<()>) { self.0.push(node.clone()) }
+}
+
+#[test]
+fn walk() {
+    // -1 + 6 / 2
+    let asa = parse_nodes([MyNode::Neg, MyNode::Number(1), MyNode::Add, MyNode::Number(6), MyNode::Div, MyNode::Number(2)]);
+    assert_eq!(visit::walk(&asa, &mut Evaluator), 2);
+
+    // children are visited in order before their parents
+    let mut order = Order(Vec::new());
+    visit::walk(&asa, &mut order);
+    assert_eq!(order.0, [MyNode::Number(1), MyNode::Neg, MyNode::Number(6), MyNode::Number(2), MyNode::Div, MyNode::Add]);
+}
+
+#[test]
+fn walk_mixfix_and_nary() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+
+    // if 0 then 1 else sum(2, 3 + 4, sum())
+    parse::node(MyNode::If, &mut asa).unwrap();
+    parse::node(MyNode::Number(0), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(1), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Sum(0), &mut asa).unwrap();
+    parse::node(MyNode::Number(2), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Number(3), &mut asa).unwrap();
+    parse::node(MyNode::Add, &mut asa).unwrap();
+    parse::node(MyNode::Number(4), &mut asa).unwrap();
+    parse::separator(&mut asa).unwrap();
+    parse::node(MyNode::Sum(0), &mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::close(&mut asa).unwrap();
+    parse::ensure_completed(&mut asa).unwrap();
+
+    assert_eq!(visit::walk(&asa, &mut Evaluator), 9);
+
+    // the 'parameters' of mixfix and n-ary nodes are visited in order too
+    let mut order = Order(Vec::new());
+    visit::walk(&asa, &mut order);
+    assert_eq!(order.0, [
+        MyNode::Number(0), MyNode::Number(1),
+        MyNode::Number(2), MyNode::Number(3), MyNode::Number(4), MyNode::Add, MyNode::Sum(0), MyNode::Sum(3),
+        MyNode::If,
+    ]);
+
+    // subtree views can be walked too
+    use ketchup::view::View;
+    assert_eq!(visit::walk(&asa.subtree(5), &mut Evaluator), 7);
+}
+
+#[test]
+fn walk_deep() {
+    // ----...1 (deep enough to overflow the call stack of a recursive walker)
+    let depth = 1_000_000;
+    let asa = parse_nodes(std::iter::repeat(MyNode::Neg).take(depth).chain([MyNode::Number(1)]));
+    assert_eq!(visit::walk(&asa, &mut Evaluator), 1);
+}
+
+struct CheckedEvaluator;
+
+impl TryVisitor<MyNode> for CheckedEvaluator {
+    type Output = i32;
+    type Error = &'static str;
+
+    fn visit_operand(&mut self, node: &MyNode) -> Result<i32, Self::Error> {
+        Ok(Evaluator.visit_operand(node))
+    }
+
+    fn visit_unary(&mut self, node: &MyNode, child: i32) -> Result<i32, Self::Error> {
+        Ok(Evaluator.visit_unary(node, child))
+    }
+
+    fn visit_binary(&mut self, node: &MyNode, lhs: i32, rhs: i32) -> Result<i32, Self::Error> {
+        match node {
+            MyNode::Div if rhs == 0 => Err("division by zero"),
+            _ => Ok(Evaluator.visit_binary(node, lhs, rhs)),
+        }
+    }
+
+    fn visit_mixfix(&mut self, node: &MyNode, params: Vec<i32>) -> Result<i32, Self::Error> {
+        Ok(Evaluator.visit_mixfix(node, params))
+    }
+
+    fn visit_nary(&mut self, node: &MyNode, params: Vec<i32>) -> Result<i32, Self::Error> {
+        Ok(Evaluator.visit_nary(node, params))
+    }
+
+    fn visit_postfix(&mut self, node: &MyNode, operand: i32, args: Vec<i32>) -> Result<i32, Self::Error> {
+        Ok(Evaluator.visit_postfix(node, operand, args))
+    }
+}
+
+#[test]
+fn try_walk() {
+    let asa = parse_nodes([MyNode::Number(6), MyNode::Div, MyNode::Number(2)]);
+    assert_eq!(visit::try_walk(&asa, &mut CheckedEvaluator), Ok(3));
+
+    let asa = parse_nodes([MyNode::Number(1), MyNode::Add, MyNode::Number(6), MyNode::Div, MyNode::Number(0)]);
+    assert_eq!(visit::try_walk(&asa, &mut CheckedEvaluator), Err("division by zero"));
+}
+
+/// Negates every number, and counts the nodes
+struct Negator;
+
+impl VisitorMut<MyNode> for Negator {
+    type Output = usize;
+
+    fn visit_operand(&mut self, node: &mut MyNode) -> usize {
+        if let MyNode::Number(num) = node {
+            *num = -*num;
+        }
+        1
+    }
+
+    fn visit_unary(&mut self, _node: &mut MyNode, child: usize) -> usize {
+        child + 1
+    }
+
+    fn visit_binary(&mut self, _node: &mut MyNode, lhs: usize, rhs: usize) -> usize {
+        lhs + rhs + 1
+    }
+
+    fn visit_mixfix(&mut self, _node: &mut MyNode, params: Vec<usize>) -> usize {
+        params.into_iter().sum::<usize>() + 1
+    }
+
+    fn visit_nary(&mut self, _node: &mut MyNode, params: Vec<usize>) -> usize {
+        params.into_iter().sum::<usize>() + 1
+    }
+
+    fn visit_postfix(&mut self, _node: &mut MyNode, operand: usize, args: Vec<usize>) -> usize {
+        operand + args.into_iter().sum::<usize>() + 1
+    }
+}
+
+#[test]
+fn walk_mut() {
+    let mut asa = parse_nodes([MyNode::Number(1), MyNode::Add, MyNode::Neg, MyNode::Number(2)]);
+    assert_eq!(visit::walk_mut(&mut asa, &mut Negator), 4);
+    assert_eq!(asa.vector[..], [MyNode::Add, MyNode::Number(-1), MyNode::Neg, MyNode::Number(-2)]);
+}
+
+#[test]
+#[should_panic]
+fn walk_incomplete() {
+    let mut asa = VectorASA::<MyNode>::new(MyNode::MAX_PRECEDENCE);
+    parse::nodes([MyNode::Number(1), MyNode::Add], &mut asa).unwrap_err();
+    visit::walk(&asa, &mut Evaluator); // should panic
+}